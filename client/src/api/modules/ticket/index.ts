@@ -9,8 +9,8 @@ export const createTicket = () => ({
   getDetail (id: number) {
     return makeRequest<TicketDetail>(instance(`/project/ticket/tickets/${id}`))
   },
-  process (id: number, flow: TicketProcessFlow) {
-    return makeRequest<void, Record<string, string>>(instance(`/project/ticket/tickets/${id}/process`, { method: 'POST', body: { flow } }))
+  process (id: number, flow: TicketProcessFlow, version: number) {
+    return makeRequest<void, Record<string, string>>(instance(`/project/ticket/tickets/${id}/process`, { method: 'POST', body: { flow, version } }))
   },
   getProbablySchemas () {
     return makeRequest<TicketSchema[]>(instance('/project/ticket/schemas'))
@@ -178,6 +178,7 @@ export interface TicketFlowItem {
   user_id?: string
   ticket_schema_flow_id: number
   finished: boolean
+  version: number
   created_at: Date
   updated_at: Date
   module: TicketFlowValue
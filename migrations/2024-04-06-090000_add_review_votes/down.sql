@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE `ticket_reviews`
+ADD CONSTRAINT `unique_ticket_flow_id_ticket_schema_review_id` UNIQUE (`ticket_flow_id`, `ticket_schema_review_id`);
+ALTER TABLE `ticket_reviews` DROP INDEX `unique_ticket_flow_id_ticket_schema_review_id_user_id`;
+
+ALTER TABLE `ticket_reviews` DROP FOREIGN KEY `fk_ticket_reviews_user_id`;
+ALTER TABLE `ticket_reviews` DROP COLUMN `user_id`;
+
+ALTER TABLE `ticket_schema_reviews` DROP COLUMN `vote`;
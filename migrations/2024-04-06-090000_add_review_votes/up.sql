@@ -0,0 +1,14 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_schema_reviews` ADD COLUMN `vote` JSON;
+
+ALTER TABLE `ticket_reviews` ADD COLUMN `user_id` CHAR(36);
+ALTER TABLE `ticket_reviews`
+ADD CONSTRAINT `fk_ticket_reviews_user_id` FOREIGN KEY (`user_id`) REFERENCES `users`(`id`);
+
+UPDATE `ticket_reviews`
+INNER JOIN `ticket_flows` ON `ticket_flows`.`id` = `ticket_reviews`.`ticket_flow_id`
+SET `ticket_reviews`.`user_id` = `ticket_flows`.`user_id`;
+
+ALTER TABLE `ticket_reviews`
+ADD CONSTRAINT `unique_ticket_flow_id_ticket_schema_review_id_user_id` UNIQUE (`ticket_flow_id`, `ticket_schema_review_id`, `user_id`);
+ALTER TABLE `ticket_reviews` DROP INDEX `unique_ticket_flow_id_ticket_schema_review_id`;
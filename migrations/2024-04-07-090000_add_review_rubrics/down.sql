@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE `ticket_reviews` DROP COLUMN `scores`;
+
+ALTER TABLE `ticket_schema_reviews` DROP COLUMN `rubric`;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_schema_reviews` ADD COLUMN `rubric` JSON;
+
+ALTER TABLE `ticket_reviews` ADD COLUMN `scores` JSON;
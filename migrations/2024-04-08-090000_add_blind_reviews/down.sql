@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE `ticket_schema_form_fields` DROP COLUMN `identifying`;
+
+ALTER TABLE `ticket_schema_reviews` DROP COLUMN `anonymous`;
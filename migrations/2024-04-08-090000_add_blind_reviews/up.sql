@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_schema_reviews` ADD COLUMN `anonymous` BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE `ticket_schema_form_fields` ADD COLUMN `identifying` BOOLEAN NOT NULL DEFAULT FALSE;
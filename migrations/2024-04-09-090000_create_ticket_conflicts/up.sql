@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE `ticket_conflicts`(
+	`id` INTEGER NOT NULL AUTO_INCREMENT,
+	`ticket_schema_id` INTEGER NOT NULL,
+	`ticket_id` INTEGER,
+	`user_id` CHAR(36) NOT NULL,
+	`field_key` VARCHAR(100),
+	`field_value` VARCHAR(255),
+	`reason` TEXT,
+	`created_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	`updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+	FOREIGN KEY (`ticket_schema_id`) REFERENCES `ticket_schemas`(`id`),
+	FOREIGN KEY (`ticket_id`) REFERENCES `tickets`(`id`),
+	FOREIGN KEY (`user_id`) REFERENCES `users`(`id`),
+	PRIMARY KEY(`id`)
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS `ticket_flow_assignees`;
+
+ALTER TABLE `ticket_schema_flows` DROP COLUMN `assignment`;
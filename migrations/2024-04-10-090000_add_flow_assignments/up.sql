@@ -0,0 +1,14 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_schema_flows` ADD COLUMN `assignment` JSON;
+
+CREATE TABLE `ticket_flow_assignees`(
+	`id` INTEGER NOT NULL AUTO_INCREMENT,
+	`ticket_flow_id` INTEGER NOT NULL,
+	`user_id` CHAR(36) NOT NULL,
+	`created_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	`updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+	FOREIGN KEY (`ticket_flow_id`) REFERENCES `ticket_flows`(`id`),
+	FOREIGN KEY (`user_id`) REFERENCES `users`(`id`),
+	UNIQUE KEY `unique_ticket_flow_id_user_id` (`ticket_flow_id`, `user_id`),
+	PRIMARY KEY(`id`)
+);
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+CREATE TABLE `ticket_histories`(
+	`id` INTEGER NOT NULL AUTO_INCREMENT,
+	`ticket_id` INTEGER NOT NULL,
+	`ticket_flow_id` INTEGER,
+	`user_id` CHAR(36),
+	`action` VARCHAR(50) NOT NULL,
+	`detail` JSON,
+	`created_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	`updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+	FOREIGN KEY (`ticket_id`) REFERENCES `tickets`(`id`),
+	FOREIGN KEY (`ticket_flow_id`) REFERENCES `ticket_flows`(`id`),
+	FOREIGN KEY (`user_id`) REFERENCES `users`(`id`),
+	PRIMARY KEY(`id`)
+);
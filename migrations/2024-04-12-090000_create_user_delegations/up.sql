@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE `user_delegations`(
+	`id` INTEGER NOT NULL AUTO_INCREMENT,
+	`user_id` CHAR(36) NOT NULL,
+	`delegate_id` CHAR(36) NOT NULL,
+	`started_at` TIMESTAMP NOT NULL,
+	`ended_at` TIMESTAMP NOT NULL,
+	`created_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	`updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+	FOREIGN KEY (`user_id`) REFERENCES `users`(`id`),
+	FOREIGN KEY (`delegate_id`) REFERENCES `users`(`id`),
+	PRIMARY KEY(`id`)
+);
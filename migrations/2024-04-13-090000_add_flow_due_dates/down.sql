@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE `ticket_flows` DROP COLUMN `escalated_at`;
+ALTER TABLE `ticket_flows` DROP COLUMN `due_at`;
+
+ALTER TABLE `ticket_schema_flows` DROP COLUMN `due_hours`;
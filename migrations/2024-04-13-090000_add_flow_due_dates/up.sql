@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_schema_flows` ADD COLUMN `due_hours` INTEGER;
+
+ALTER TABLE `ticket_flows` ADD COLUMN `due_at` TIMESTAMP NULL;
+ALTER TABLE `ticket_flows` ADD COLUMN `escalated_at` TIMESTAMP NULL;
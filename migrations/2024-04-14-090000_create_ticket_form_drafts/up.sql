@@ -0,0 +1,15 @@
+-- Your SQL goes here
+CREATE TABLE `ticket_form_drafts`(
+	`id` INTEGER NOT NULL AUTO_INCREMENT,
+	`ticket_flow_id` INTEGER NOT NULL,
+	`ticket_schema_form_id` INTEGER NOT NULL,
+	`user_id` CHAR(36) NOT NULL,
+	`value` JSON NOT NULL,
+	`created_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	`updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+	FOREIGN KEY (`ticket_flow_id`) REFERENCES `ticket_flows`(`id`),
+	FOREIGN KEY (`ticket_schema_form_id`) REFERENCES `ticket_schema_forms`(`id`),
+	FOREIGN KEY (`user_id`) REFERENCES `users`(`id`),
+	UNIQUE KEY `unique_ticket_flow_id_ticket_schema_form_id` (`ticket_flow_id`, `ticket_schema_form_id`),
+	PRIMARY KEY(`id`)
+);
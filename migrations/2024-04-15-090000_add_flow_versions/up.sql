@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_flows` ADD COLUMN `version` INTEGER NOT NULL DEFAULT 0;
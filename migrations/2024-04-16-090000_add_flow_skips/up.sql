@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_flows` ADD COLUMN `skipped` BOOLEAN NOT NULL DEFAULT FALSE;
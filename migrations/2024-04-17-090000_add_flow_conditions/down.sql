@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE `ticket_schema_flows` DROP COLUMN `condition`;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS `ticket_schema_spawns`;
+
+ALTER TABLE `tickets` DROP FOREIGN KEY `fk_tickets_parent_id`;
+ALTER TABLE `tickets` DROP COLUMN `prefill`;
+ALTER TABLE `tickets` DROP COLUMN `parent_id`;
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+ALTER TABLE `tickets` ADD COLUMN `parent_id` INTEGER;
+ALTER TABLE `tickets` ADD COLUMN `prefill` JSON;
+ALTER TABLE `tickets` ADD CONSTRAINT `fk_tickets_parent_id` FOREIGN KEY (`parent_id`) REFERENCES `tickets`(`id`);
+
+CREATE TABLE `ticket_schema_spawns`(
+	`id` INTEGER NOT NULL AUTO_INCREMENT,
+	`ticket_schema_id` INTEGER NOT NULL,
+	`target_schema_id` INTEGER NOT NULL,
+	`mappings` JSON NOT NULL,
+	`operators` JSON NOT NULL,
+	`created_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	`updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+	FOREIGN KEY (`ticket_schema_id`) REFERENCES `ticket_schemas`(`id`),
+	FOREIGN KEY (`target_schema_id`) REFERENCES `ticket_schemas`(`id`),
+	PRIMARY KEY(`id`)
+);
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_schema_forms` ADD COLUMN `rules` JSON;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM `ticket_form_drafts`;
+ALTER TABLE `ticket_form_drafts`
+	ADD UNIQUE KEY `unique_ticket_flow_id_ticket_schema_form_id` (`ticket_flow_id`, `ticket_schema_form_id`),
+	DROP INDEX `unique_ticket_flow_id_ticket_schema_form_id_user_id`;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_form_drafts`
+	ADD UNIQUE KEY `unique_ticket_flow_id_ticket_schema_form_id_user_id` (`ticket_flow_id`, `ticket_schema_form_id`, `user_id`),
+	DROP INDEX `unique_ticket_flow_id_ticket_schema_form_id`;
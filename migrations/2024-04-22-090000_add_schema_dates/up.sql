@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE `ticket_schemas` ADD COLUMN `dates` JSON;
@@ -13,6 +13,7 @@ pub struct AppError {
     status: Status,
     message: String,
     fields: Option<serde_json::Map<String, Value>>,
+    data: Option<Value>,
 }
 
 impl AppError {
@@ -21,6 +22,7 @@ impl AppError {
             status: Status::NotFound,
             message: message,
             fields: None,
+            data: None,
         }
     }
     pub fn unauthorized(i18n: I18n) -> AppError {
@@ -28,6 +30,7 @@ impl AppError {
             status: Status::Unauthorized,
             message: i18n.t("error.unauthorized"),
             fields: None,
+            data: None,
         }
     }
     pub fn bad_request(message: String) -> AppError {
@@ -35,6 +38,7 @@ impl AppError {
             status: Status::BadRequest,
             message,
             fields: None,
+            data: None,
         }
     }
     pub fn bad_request_with_fields(i18n: I18n, fields: serde_json::Map<String, Value>) -> AppError {
@@ -42,6 +46,15 @@ impl AppError {
             status: Status::BadRequest,
             message: i18n.t("error.wrong_fields"),
             fields: Some(fields),
+            data: None,
+        }
+    }
+    pub fn conflict(message: String, data: Value) -> AppError {
+        AppError {
+            status: Status::Conflict,
+            message,
+            fields: None,
+            data: Some(data),
         }
     }
     pub fn forbidden(message: String) -> AppError {
@@ -49,6 +62,7 @@ impl AppError {
             status: Status::Forbidden,
             message: message,
             fields: None,
+            data: None,
         }
     }
     pub fn too_many_requests(i18n: I18n) -> AppError {
@@ -56,6 +70,7 @@ impl AppError {
             status: Status::TooManyRequests,
             message: i18n.t("error.too_many_requests"),
             fields: None,
+            data: None,
         }
     }
     pub fn unknown_host(i18n: I18n) -> AppError {
@@ -63,6 +78,7 @@ impl AppError {
             status: Status::BadRequest,
             message: i18n.t("error.unknown_host"),
             fields: None,
+            data: None,
         }
     }
 
@@ -71,6 +87,7 @@ impl AppError {
             status: Status::InternalServerError,
             message: message,
             fields: None,
+            data: None,
         }
     }
 }
@@ -80,6 +97,14 @@ struct ErrorResponse {
     status: String,
     message: String,
     fields: Option<serde_json::Map<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl From<diesel::result::Error> for AppError {
+    fn from(err: diesel::result::Error) -> AppError {
+        AppError::internal(err.to_string())
+    }
 }
 
 impl<'r> Responder<'r, 'static> for AppError {
@@ -88,6 +113,7 @@ impl<'r> Responder<'r, 'static> for AppError {
             status: self.status.to_string(),
             message: self.message.to_owned(),
             fields: self.fields,
+            data: self.data,
         })
         .expect("Failed to serialize error message");
 
@@ -28,13 +28,13 @@ mod modules;
 mod schema;
 mod utils;
 
-#[derive(Database)]
+#[derive(Database, Clone)]
 #[database("main_db")]
 pub struct MainDb(MysqlPool);
 
 pub type DbConn = Connection<MainDb>;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 pub struct AppConfig {
     secret_key: String,
     smtp_url: String,
@@ -3,6 +3,7 @@ pub mod project;
 pub mod role;
 pub mod target;
 pub mod user;
+pub mod user_delegation;
 pub mod user_email;
 pub mod user_label;
 pub mod user_session;
@@ -0,0 +1,118 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rocket_db_pools::diesel::prelude::RunQueryDsl;
+
+use crate::models::user::User;
+use crate::schema::{user_delegations, users};
+use crate::utils::serde::unix_time;
+
+#[derive(
+    Queryable,
+    Identifiable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    AsChangeset,
+)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = user_delegations)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct UserDelegation {
+    pub id: i32,
+    pub user_id: String,
+    pub delegate_id: String,
+    #[serde(with = "unix_time")]
+    pub started_at: NaiveDateTime,
+    #[serde(with = "unix_time")]
+    pub ended_at: NaiveDateTime,
+    #[serde(with = "unix_time")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "unix_time")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl UserDelegation {
+    pub async fn create(
+        conn: &mut crate::DbConn,
+        user: &User,
+        delegate: &User,
+        started_at: NaiveDateTime,
+        ended_at: NaiveDateTime,
+    ) -> Result<UserDelegation, diesel::result::Error> {
+        diesel::insert_into(user_delegations::table)
+            .values((
+                user_delegations::user_id.eq(user.id.clone()),
+                user_delegations::delegate_id.eq(delegate.id.clone()),
+                user_delegations::started_at.eq(started_at),
+                user_delegations::ended_at.eq(ended_at),
+            ))
+            .execute(conn)
+            .await?;
+
+        sql_function! {
+            fn last_insert_id() -> Integer;
+        }
+
+        user_delegations::table
+            .find(last_insert_id())
+            .first(conn)
+            .await
+    }
+
+    pub async fn find(
+        conn: &mut crate::DbConn,
+        id: i32,
+    ) -> Result<UserDelegation, diesel::result::Error> {
+        user_delegations::table.find(id).first(conn).await
+    }
+
+    pub async fn delete(&self, conn: &mut crate::DbConn) -> Result<usize, diesel::result::Error> {
+        diesel::delete(user_delegations::table.find(self.id))
+            .execute(conn)
+            .await
+    }
+
+    pub async fn get_user_delegations(
+        conn: &mut crate::DbConn,
+        user: &User,
+    ) -> Result<Vec<(UserDelegation, User)>, diesel::result::Error> {
+        user_delegations::table
+            .inner_join(users::table.on(users::id.eq(user_delegations::delegate_id)))
+            .filter(user_delegations::user_id.eq(user.id.clone()))
+            .order(user_delegations::started_at.desc())
+            .select((UserDelegation::as_select(), User::as_select()))
+            .load(conn)
+            .await
+    }
+
+    pub async fn get_delegated_delegations(
+        conn: &mut crate::DbConn,
+        delegate: &User,
+    ) -> Result<Vec<(UserDelegation, User)>, diesel::result::Error> {
+        user_delegations::table
+            .inner_join(users::table.on(users::id.eq(user_delegations::user_id)))
+            .filter(user_delegations::delegate_id.eq(delegate.id.clone()))
+            .order(user_delegations::started_at.desc())
+            .select((UserDelegation::as_select(), User::as_select()))
+            .load(conn)
+            .await
+    }
+
+    pub async fn get_active_delegator_ids(
+        conn: &mut crate::DbConn,
+        delegate: &User,
+    ) -> Result<Vec<String>, diesel::result::Error> {
+        let now = chrono::Utc::now().naive_utc();
+        user_delegations::table
+            .filter(user_delegations::delegate_id.eq(delegate.id.clone()))
+            .filter(user_delegations::started_at.le(now))
+            .filter(user_delegations::ended_at.ge(now))
+            .select(user_delegations::user_id)
+            .distinct()
+            .load(conn)
+            .await
+    }
+}
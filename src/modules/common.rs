@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use lettre::{message::header::ContentType, Message};
 use rocket::{
     http::{Cookie, CookieJar},
@@ -8,13 +9,17 @@ use rocket::{
 
 use crate::{
     error::AppError,
-    models::{project::Project, user::User, user_email::UserEmail, user_session::UserSession},
+    models::{
+        project::Project, user::User, user_delegation::UserDelegation, user_email::UserEmail,
+        user_session::UserSession,
+    },
     modules::{EmptyResponse, EmptyResult, JsonResult},
     utils::{
         i18n::I18n,
         jwt::{self, LoginClaims},
         lettre::send_email,
         rocket::{PrefixUri, UserAgent},
+        serde::unix_time,
     },
     AppConfig, DbConn,
 };
@@ -168,6 +173,122 @@ async fn get_me(auth: AuthGuard) -> JsonResult<User> {
     Ok(Json(user))
 }
 
+#[derive(Serialize)]
+struct UserDelegationItem {
+    #[serde(flatten)]
+    delegation: UserDelegation,
+    user: User,
+}
+
+#[derive(Serialize)]
+struct UserDelegations {
+    delegations: Vec<UserDelegationItem>,
+    delegated: Vec<UserDelegationItem>,
+}
+
+#[get("/project/me/delegations")]
+async fn get_my_delegations(mut conn: DbConn, auth: AuthGuard) -> JsonResult<UserDelegations> {
+    let AuthGuard { user, .. } = auth;
+
+    let delegations = UserDelegation::get_user_delegations(&mut conn, &user)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let delegated = UserDelegation::get_delegated_delegations(&mut conn, &user)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let into_items = |items: Vec<(UserDelegation, User)>| {
+        items
+            .into_iter()
+            .map(|(delegation, user)| UserDelegationItem { delegation, user })
+            .collect::<Vec<_>>()
+    };
+
+    Ok(Json(UserDelegations {
+        delegations: into_items(delegations),
+        delegated: into_items(delegated),
+    }))
+}
+
+#[derive(Deserialize)]
+struct DelegationReq {
+    delegate_id: String,
+    #[serde(with = "unix_time")]
+    started_at: NaiveDateTime,
+    #[serde(with = "unix_time")]
+    ended_at: NaiveDateTime,
+}
+
+#[post("/project/me/delegations", data = "<delegation_req>")]
+async fn add_my_delegation<'a>(
+    mut conn: DbConn,
+    i18n: I18n<'a>,
+    auth: AuthGuard,
+    delegation_req: Json<DelegationReq>,
+) -> JsonResult<UserDelegation> {
+    let AuthGuard { user, .. } = auth;
+
+    if delegation_req.delegate_id == user.id {
+        return Err(AppError::bad_request(
+            i18n.t("delegation.error.cannot_delegate_to_self"),
+        ));
+    }
+
+    if delegation_req.ended_at <= delegation_req.started_at {
+        return Err(AppError::bad_request(
+            i18n.t("delegation.error.invalid_date_range"),
+        ));
+    }
+
+    let delegate = User::find(&mut conn, delegation_req.delegate_id.clone())
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+
+    if delegate.project_id != user.project_id {
+        return Err(AppError::not_found(
+            "Delegate is not in this project".to_owned(),
+        ));
+    }
+
+    UserDelegation::create(
+        &mut conn,
+        &user,
+        &delegate,
+        delegation_req.started_at,
+        delegation_req.ended_at,
+    )
+    .await
+    .map(Json)
+    .map_err(|err| AppError::internal(err.to_string()))
+}
+
+#[delete("/project/me/delegations/<id>")]
+async fn delete_my_delegation<'a>(
+    mut conn: DbConn,
+    i18n: I18n<'a>,
+    auth: AuthGuard,
+    id: i32,
+) -> EmptyResult {
+    let AuthGuard { user, .. } = auth;
+
+    let delegation = UserDelegation::find(&mut conn, id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+
+    if delegation.user_id != user.id {
+        return Err(AppError::forbidden(
+            i18n.t("delegation.error.not_owner_of_this_delegation"),
+        ));
+    }
+
+    delegation
+        .delete(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    Ok(EmptyResponse)
+}
+
 #[post("/project/logout")]
 async fn logout(mut conn: DbConn, cookie_jar: &CookieJar<'_>, auth: AuthGuard) -> EmptyResult {
     let AuthGuard { user_session, .. } = auth;
@@ -204,6 +325,9 @@ pub fn routes() -> Vec<Route> {
         login,
         verify_token,
         get_me,
+        get_my_delegations,
+        add_my_delegation,
+        delete_my_delegation,
         logout,
         get_features_by_user
     ]
@@ -71,5 +71,6 @@ pub fn stage() -> AdHoc {
                     catch_too_many_requests
                 ],
             )
+            .attach(ticket::escalations::stage())
     })
 }
@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 use std::iter;
 
+use rocket::data::Data;
+use rocket::data::ToByteUnit;
 use rocket::form::Form;
 use rocket::fs::NamedFile;
 use rocket::fs::TempFile;
+use rocket::request::FromParam;
 use rocket::serde::json::serde_json;
 use rocket::serde::json::Json;
 use rocket::serde::json::Value;
@@ -13,16 +16,35 @@ use rocket_db_pools::diesel::scoped_futures::ScopedFutureExt;
 use rocket_db_pools::diesel::AsyncConnection;
 use serde_json::Map;
 
+use super::assignments::models::TicketFlowAssignee;
+use super::assignments::TicketFlowAssignment;
+use super::conflicts::models::TicketConflict;
+use super::forms::dates::TicketSchemaDates;
 use super::forms::fields::FormFieldDefine;
 use super::forms::fields::FormSchemaField;
 use super::forms::models::TicketFormAnswer;
+use super::forms::models::TicketFormDraft;
 use super::forms::models::TicketFormFile;
 use super::forms::models::TicketFormImage;
 use super::forms::models::TicketSchemaForm;
+use super::forms::models::TicketSchemaFormField;
+use super::forms::numbers::FormNumberSummary;
+use super::forms::FormSchema;
+use super::histories::models::TicketHistory;
+use super::histories::TicketHistoryAction;
+use super::histories::TicketHistoryItem;
+use super::models::TicketFlow;
 use super::models::TicketSchema;
 use super::models::TicketSchemaFlow;
 use super::reviews::models::TicketReview;
 use super::reviews::models::TicketSchemaReview;
+use super::reviews::rubrics::ReviewRubric;
+use super::reviews::rubrics::TicketReviewScore;
+use super::spawns::models::TicketSchemaSpawn;
+use super::spawns::TicketSpawnMappings;
+use super::spawns::TicketSpawnOperatorFrom;
+use super::spawns::TicketSpawnOperators;
+use super::TicketFlowAccess;
 use super::TicketFlowItem;
 use super::TicketFlowStatus;
 use super::TicketFlowValue;
@@ -34,9 +56,11 @@ use super::TicketWithStatus;
 use crate::error::AppError;
 use crate::models::target::Target;
 use crate::models::user::User;
+use crate::models::user_email::UserEmail;
 use crate::modules::ticket::models::Ticket;
 use crate::modules::ApiResult;
 use crate::modules::{guard::AuthGuard, EmptyResponse, EmptyResult, JsonResult};
+use crate::utils::csv;
 use crate::utils::i18n::I18n;
 use crate::DataFolder;
 use crate::DbConn;
@@ -44,10 +68,29 @@ use crate::DbConn;
 #[get("/ticket/tickets")]
 async fn all_tickets(mut conn: DbConn, auth: AuthGuard) -> JsonResult<Vec<TicketWithStatus>> {
     let AuthGuard { user, .. } = auth;
-    let tickets = Ticket::get_tickets_by_user(&mut conn, &user)
+    let mut tickets = match Ticket::get_tickets_by_user(&mut conn, &user).await {
+        Ok(tickets) => tickets,
+        Err(_) => return Ok(Json(vec![])),
+    };
+
+    let blind_schema_ids = Ticket::get_blind_schema_ids(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let blind_candidates = tickets
+        .iter()
+        .filter(|ticket| blind_schema_ids.contains(&ticket.ticket.ticket_schema_id))
+        .map(|ticket| &ticket.ticket)
+        .collect::<Vec<_>>();
+    let blind_ticket_ids = Ticket::get_blind_ticket_ids(&mut conn, &user, &blind_candidates)
         .await
-        .map_or(Json(vec![]), Json);
-    Ok(tickets)
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    for ticket in tickets.iter_mut() {
+        if blind_ticket_ids.contains(&ticket.ticket.id) {
+            ticket.ticket.redact();
+        }
+    }
+
+    Ok(Json(tickets))
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -56,6 +99,10 @@ pub struct TicketDetail {
     pub ticket: TicketWithStatus,
     pub schema: TicketSchema,
     pub flows: Vec<TicketFlowStatus>,
+    pub histories: Vec<TicketHistoryItem>,
+    pub draft: Option<TicketFormDraft>,
+    pub parent: Option<Ticket>,
+    pub children: Vec<Ticket>,
 }
 
 #[get("/ticket/tickets/<ticket_id>")]
@@ -95,7 +142,7 @@ async fn get_ticket<'a>(
             .take(schema_flows.len().saturating_sub(status_flows.len())),
     );
 
-    let flows = schema_flows
+    let mut flows = schema_flows
         .into_iter()
         .zip(status_flows.into_iter())
         .map(|(schema_flow, flow)| TicketFlowStatus {
@@ -109,42 +156,115 @@ async fn get_ticket<'a>(
         false => {
             let process_flow = flows.iter().find(|f| {
                 if let Some(flow) = &f.flow {
-                    flow.flow.finished == false
+                    !flow.flow.finished && !flow.flow.skipped
                 } else {
                     false
                 }
             });
 
             match process_flow {
-                Some(flow) => match flow.flow.as_ref().and_then(|f| f.flow.user_id.clone()) {
-                    Some(flow_user_id) if flow_user_id == user.id => TicketStatus::Pending,
-                    Some(_) => TicketStatus::InProgress,
-                    None => {
-                        let target = Target::find(&mut conn, flow.schema.schema.operator_id)
-                            .await
-                            .map_err(|err| AppError::internal(err.to_string()))?;
-                        let is_user = Target::is_user_in_targets(&mut conn, &user, &vec![target])
-                            .await
-                            .map_err(|err| AppError::internal(err.to_string()))?;
-
-                        match is_user {
-                            true => TicketStatus::Pending,
-                            false => TicketStatus::InProgress,
+                Some(TicketFlowStatus {
+                    flow: Some(flow), ..
+                }) => {
+                    let access = flow
+                        .flow
+                        .get_eligible_access(&mut conn, &user)
+                        .await
+                        .map_err(|err| AppError::internal(err.to_string()))?;
+                    match access {
+                        TicketFlowAccess::Direct
+                        | TicketFlowAccess::OnBehalfOf(_)
+                        | TicketFlowAccess::Open => TicketStatus::Pending,
+                        TicketFlowAccess::Denied | TicketFlowAccess::Conflicted => {
+                            TicketStatus::InProgress
                         }
                     }
-                },
+                }
+                Some(_) => TicketStatus::InProgress,
                 None => TicketStatus::Finished,
             }
         }
     };
 
+    let mut histories = TicketHistory::get_ticket_histories(&mut conn, &ticket)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let is_blind_user = ticket
+        .is_blind_user(&mut conn, &user)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    if is_blind_user {
+        for history in histories.iter_mut() {
+            if history.history.user_id.as_ref() != Some(&user.id) {
+                history.history.user_id = None;
+                history.user = None;
+            }
+            history.history.detail = None;
+        }
+        let identifying_keys = flows
+            .iter()
+            .flat_map(|flow| flow.schema.get_identifying_keys())
+            .collect::<Vec<_>>();
+        for flow in flows.iter_mut() {
+            flow.schema.redact(&identifying_keys);
+            if let Some(flow) = &mut flow.flow {
+                flow.redact(&user, &identifying_keys);
+            }
+        }
+    }
+    for flow in flows.iter_mut() {
+        flow.schema.localize(i18n.locale);
+        flow.schema
+            .fill_remaining(&mut conn, Some(ticket.id))
+            .await
+            .map_err(|err| AppError::internal(err.to_string()))?;
+    }
+
+    let draft = match ticket_status {
+        TicketStatus::Pending => {
+            let process_flow = flows
+                .iter()
+                .filter_map(|flow| flow.flow.as_ref())
+                .find(|flow| !flow.flow.finished && !flow.flow.skipped);
+            match process_flow {
+                Some(flow) => TicketFormDraft::find_by_flow(&mut conn, &flow.flow, &user)
+                    .await
+                    .map_err(|err| AppError::internal(err.to_string()))?,
+                None => None,
+            }
+        }
+        _ => None,
+    };
+
+    let mut parent = ticket
+        .get_parent(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let mut children = ticket
+        .get_children(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let mut ticket = Ticket::with_status(&mut conn, vec![(ticket, ticket_status)])
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?
+        .remove(0);
+    if is_blind_user {
+        ticket.ticket.redact();
+        for related in parent.iter_mut().chain(children.iter_mut()) {
+            related.redact();
+        }
+    }
+
     Ok(Json(TicketDetail {
-        ticket: TicketWithStatus {
-            ticket,
-            status: ticket_status,
-        },
+        ticket,
         schema,
         flows,
+        histories,
+        draft,
+        parent,
+        children,
     }))
 }
 
@@ -152,6 +272,7 @@ async fn get_ticket<'a>(
 pub struct TicketFlowReviewReq {
     pub approved: bool,
     pub comment: Option<String>,
+    pub scores: Option<serde_json::Map<String, Value>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -164,6 +285,7 @@ pub enum TicketProcessFlow {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TicketFlowProcessReq {
     pub flow: TicketProcessFlow,
+    pub version: i32,
 }
 
 #[post("/ticket/tickets/<ticket_id>/process", data = "<flow_req>")]
@@ -194,30 +316,33 @@ async fn process_ticket_flow<'a>(
         .await
         .map_err(|err| AppError::internal(err.to_string()))?;
 
-    if let Some(flow_user_id) = &process_flow.user_id {
-        if flow_user_id != &user.id {
+    let access = process_flow
+        .get_eligible_access(&mut conn, &user)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let on_behalf_of = match access {
+        TicketFlowAccess::Denied => {
             return Err(AppError::forbidden(
                 i18n.t("ticket.error.not_assign_to_this_flow"),
             ));
         }
-    }
+        TicketFlowAccess::Conflicted => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.conflict_of_interest"),
+            ));
+        }
+        TicketFlowAccess::OnBehalfOf(user_id) => Some(user_id),
+        TicketFlowAccess::Direct | TicketFlowAccess::Open => None,
+    };
+    let acting_user_id = on_behalf_of.clone().unwrap_or(user.id.clone());
 
     let process_schema = process_flow
         .get_schema(&mut conn)
         .await
         .map_err(|err| AppError::internal(err.to_string()))?;
 
-    let schema_target = Target::find(&mut conn, process_schema.schema.operator_id)
-        .await
-        .map_err(|err| AppError::internal(err.to_string()))?;
-    let is_schema_user = Target::is_user_in_targets(&mut conn, &user, &vec![schema_target])
-        .await
-        .map_err(|err| AppError::forbidden(err.to_string()))?;
-    if !is_schema_user {
-        return Err(AppError::forbidden(
-            i18n.t("ticket.error.not_assign_to_this_schema"),
-        ));
-    }
+    let history_detail = serde_json::json!({ "on_behalf_of": on_behalf_of });
+    let stale_message = i18n.t("ticket.error.flow_was_modified");
 
     let latest_flow = ticket
         .get_latest_flow(&mut conn)
@@ -225,6 +350,7 @@ async fn process_ticket_flow<'a>(
         .map_err(|err| AppError::internal(err.to_string()))?;
 
     let req = flow_req.into_inner();
+    let version = req.version;
 
     match process_schema.module {
         TicketSchemaFlowValue::Form(form_schema) => {
@@ -233,11 +359,25 @@ async fn process_ticket_flow<'a>(
                     .validate_and_normalize(&mut conn, &i18n, &form_data)
                     .await
                 {
-                    Ok(normalized_data) => {
+                    Ok(mut normalized_data) => {
                         conn.transaction(|conn| {
                             async move {
+                                let current_flow = process_flow.lock(conn).await?;
+                                if current_flow.version != version {
+                                    return Err(AppError::conflict(
+                                        stale_message,
+                                        serde_json::to_value(&current_flow).unwrap_or_default(),
+                                    ));
+                                }
+                                if let Err(fields) = form_schema
+                                    .reserve_capacity(conn, &i18n, ticket.id, &mut normalized_data)
+                                    .await?
+                                {
+                                    return Err(AppError::bad_request_with_fields(i18n, fields));
+                                }
+                                let _ = process_flow.bump_version(conn).await?;
                                 let _ = ticket.save(conn).await?;
-                                process_flow.user_id = Some(user.id.clone());
+                                process_flow.user_id = Some(acting_user_id);
                                 process_flow.finished = true;
                                 let _ = process_flow.save(conn).await?;
                                 let _ = TicketFormAnswer::save_or_create(
@@ -247,17 +387,28 @@ async fn process_ticket_flow<'a>(
                                     normalized_data,
                                 )
                                 .await?;
+                                let _ = TicketFormDraft::delete_flow_drafts(conn, &process_flow)
+                                    .await?;
+                                let _ = TicketHistory::create(
+                                    conn,
+                                    ticket.id,
+                                    Some(&process_flow),
+                                    Some(&user),
+                                    TicketHistoryAction::Process,
+                                    Some(history_detail),
+                                )
+                                .await?;
 
                                 if latest_flow.id == process_flow.id {
                                     let _ = ticket.set_finish(conn, true).await?;
                                 }
+                                ticket.activate_process_flow(conn).await?;
 
-                                Ok::<_, diesel::result::Error>(EmptyResponse)
+                                Ok::<_, AppError>(EmptyResponse)
                             }
                             .scope_boxed()
                         })
-                        .await
-                        .map_err(|err| AppError::internal(err.to_string()))?;
+                        .await?;
                         return Ok(EmptyResponse);
                     }
                     Err(fields) => {
@@ -269,47 +420,91 @@ async fn process_ticket_flow<'a>(
         }
         TicketSchemaFlowValue::Review(review_schema) => {
             if let TicketProcessFlow::Review(review_req) = req.flow {
+                let scores = match &review_schema.rubric {
+                    Some(rubric) => {
+                        let scores = review_req.scores.unwrap_or_default();
+                        match rubric.validate_and_normalize(&i18n, &scores) {
+                            Ok(normalized_scores) => Some(Value::Object(normalized_scores)),
+                            Err(fields) => {
+                                return Err(AppError::bad_request_with_fields(i18n, fields));
+                            }
+                        }
+                    }
+                    None => None,
+                };
                 conn.transaction(|conn| {
                     async move {
+                        let current_flow = process_flow.lock(conn).await?;
+                        if current_flow.version != version {
+                            return Err(AppError::conflict(
+                                stale_message,
+                                serde_json::to_value(&current_flow).unwrap_or_default(),
+                            ));
+                        }
+                        if review_schema.vote.is_none() {
+                            let _ = process_flow.bump_version(conn).await?;
+                        }
                         let _ = ticket.save(conn).await?;
                         let _ = TicketReview::save_or_create(
                             conn,
                             &process_flow,
                             &review_schema,
+                            Some(acting_user_id.clone()),
                             review_req.approved,
                             review_req.comment,
+                            scores,
+                        )
+                        .await?;
+                        let _ = TicketHistory::create(
+                            conn,
+                            ticket.id,
+                            Some(&process_flow),
+                            Some(&user),
+                            TicketHistoryAction::Process,
+                            Some(history_detail),
                         )
                         .await?;
-                        process_flow.user_id = Some(user.id.clone());
-
-                        if review_req.approved {
-                            process_flow.finished = true;
-                            let _ = process_flow.save(conn).await?;
 
-                            if latest_flow.id == process_flow.id {
-                                let _ = ticket.set_finish(conn, true).await?;
+                        let decision = match &review_schema.vote {
+                            Some(vote) => {
+                                let votes =
+                                    TicketReview::get_flow_reviews(conn, &process_flow).await?;
+                                let eligible = TicketFlow::get_eligible_reviewer_ids(
+                                    conn,
+                                    &ticket,
+                                    &[&process_flow],
+                                )
+                                .await?;
+                                let eligible =
+                                    eligible.get(&process_flow.id).map_or(0, |ids| ids.len());
+                                vote.tally(&votes, eligible).decision
+                            }
+                            None => {
+                                process_flow.user_id = Some(acting_user_id);
+                                Some(review_req.approved)
                             }
-                        } else if review_schema.restarted {
-                            let _ = process_flow.save(conn).await?;
-                            let flows = ticket.get_flows(conn).await?;
-                            for mut flow in flows.into_iter() {
-                                flow.flow.finished = false;
-                                let _ = flow.flow.save(conn).await?;
+                        };
+
+                        if let Some(approved) = decision {
+                            if review_schema.vote.is_some() {
+                                let _ = process_flow.bump_version(conn).await?;
                             }
-                        } else {
-                            let _ = process_flow.save(conn).await?;
-                            let mut previous_flow =
-                                ticket.get_previous_flow(conn, &process_flow).await?;
-                            previous_flow.finished = false;
-                            let _ = previous_flow.save(conn).await?;
+                            ticket
+                                .apply_review_decision(
+                                    conn,
+                                    &mut process_flow,
+                                    &review_schema,
+                                    approved,
+                                )
+                                .await?;
+                            ticket.activate_process_flow(conn).await?;
                         }
 
-                        Ok::<_, diesel::result::Error>(())
+                        Ok::<_, AppError>(())
                     }
                     .scope_boxed()
                 })
-                .await
-                .map_err(|err| AppError::internal(err.to_string()))?;
+                .await?;
                 return Ok(EmptyResponse);
             }
             Err(AppError::bad_request("Invalid request".to_owned()))
@@ -317,6 +512,70 @@ async fn process_ticket_flow<'a>(
     }
 }
 
+#[put("/ticket/tickets/<ticket_id>/draft", data = "<draft_req>")]
+async fn save_ticket_flow_draft<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    ticket_id: i32,
+    draft_req: Json<serde_json::Map<String, Value>>,
+) -> JsonResult<TicketFormDraft> {
+    let AuthGuard { user, .. } = auth;
+    let ticket = Ticket::find(&mut conn, ticket_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match ticket.is_user(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_join_to_this_ticket"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let process_flow = ticket
+        .get_process_flow(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let access = process_flow
+        .get_eligible_access(&mut conn, &user)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    if let TicketFlowAccess::Denied | TicketFlowAccess::Conflicted = access {
+        return Err(AppError::forbidden(
+            i18n.t("ticket.error.not_assign_to_this_flow"),
+        ));
+    }
+
+    let process_schema = process_flow
+        .get_schema(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let form_schema = match process_schema.module {
+        TicketSchemaFlowValue::Form(form_schema) => form_schema,
+        _ => return Err(AppError::bad_request("Invalid request".to_owned())),
+    };
+
+    let normalized_data = form_schema
+        .validate_draft(&mut conn, &i18n, &draft_req)
+        .await
+        .map_err(|fields| AppError::bad_request_with_fields(i18n, fields))?;
+
+    TicketFormDraft::save_or_create(
+        &mut conn,
+        &process_flow,
+        &form_schema,
+        &user,
+        normalized_data,
+    )
+    .await
+    .map(Json)
+    .map_err(|err| AppError::internal(err.to_string()))
+}
+
 #[get("/ticket/schemas")]
 async fn all_probably_schemas(mut conn: DbConn, auth: AuthGuard) -> JsonResult<Vec<TicketSchema>> {
     let AuthGuard { user, .. } = auth;
@@ -345,21 +604,28 @@ async fn get_schema<'a>(
         Err(err) => return Err(AppError::forbidden(err.to_string())),
         _ => (),
     }
-    let flows = schema
+    let mut flows = schema
         .get_detail_flows(&mut conn)
         .await
         .map_err(|err| AppError::internal(err.to_string()))?;
+    for flow in flows.iter_mut() {
+        flow.localize(i18n.locale);
+        flow.fill_remaining(&mut conn, None)
+            .await
+            .map_err(|err| AppError::internal(err.to_string()))?;
+    }
 
     Ok(Json(TicketSchemaDetail { schema, flows }))
 }
 
-#[get("/ticket/schemas/<schema_id>/flows/<flow_id>/probably_assign_users")]
+#[get("/ticket/schemas/<schema_id>/flows/<flow_id>/probably_assign_users?<ticket_id>")]
 async fn get_probably_assign_user_in_schema_flow<'a>(
     mut conn: DbConn,
     auth: AuthGuard,
     i18n: I18n<'a>,
     schema_id: i32,
     flow_id: i32,
+    ticket_id: Option<i32>,
 ) -> JsonResult<Vec<User>> {
     let AuthGuard { user, .. } = auth;
     let schema = TicketSchema::find(&mut conn, schema_id)
@@ -378,34 +644,41 @@ async fn get_probably_assign_user_in_schema_flow<'a>(
     let schema_flow = TicketSchemaFlow::find(&mut conn, flow_id)
         .await
         .map_err(|err| AppError::not_found(err.to_string()))?;
+    if schema_flow.ticket_schema_id != schema_id {
+        return Err(AppError::bad_request("Invalid schema".to_owned()));
+    }
+    let ticket = match ticket_id {
+        Some(ticket_id) => {
+            let ticket = Ticket::find(&mut conn, ticket_id)
+                .await
+                .map_err(|err| AppError::not_found(err.to_string()))?;
+            if ticket.ticket_schema_id != schema_id {
+                return Err(AppError::bad_request("Invalid schema".to_owned()));
+            }
+            Some(ticket)
+        }
+        None => None,
+    };
 
     let users = schema_flow
-        .get_probably_assign_users(&mut conn)
+        .get_probably_assign_users(&mut conn, ticket.as_ref())
         .await
         .map_err(|err| AppError::internal(err.to_string()))?;
     Ok(Json(users))
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct AddTicketReq {
-    pub title: String,
-    pub assign_flow_users: HashMap<i32, String>,
-}
-
-#[post("/ticket/schemas/<schema_id>/tickets", data = "<new_ticket_req>")]
-async fn add_ticket_for_schema<'a>(
+#[post("/ticket/tickets/<ticket_id>/claim")]
+async fn claim_ticket_flow<'a>(
     mut conn: DbConn,
     auth: AuthGuard,
     i18n: I18n<'a>,
-    schema_id: i32,
-    new_ticket_req: Json<AddTicketReq>,
+    ticket_id: i32,
 ) -> EmptyResult {
     let AuthGuard { user, .. } = auth;
-    let schema = TicketSchema::find(&mut conn, schema_id)
+    let ticket = Ticket::find(&mut conn, ticket_id)
         .await
         .map_err(|err| AppError::not_found(err.to_string()))?;
-
-    match schema.is_probably_join_user(&mut conn, &user).await {
+    match ticket.is_user(&mut conn, &user).await {
         Ok(false) => {
             return Err(AppError::forbidden(
                 i18n.t("ticket.error.not_join_to_this_ticket"),
@@ -415,98 +688,430 @@ async fn add_ticket_for_schema<'a>(
         _ => (),
     }
 
-    let flows = schema
-        .get_flows(&mut conn)
+    let process_flow = ticket
+        .get_process_flow(&mut conn)
         .await
         .map_err(|err| AppError::not_found(err.to_string()))?;
+    if process_flow.user_id.is_some() {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.flow_already_claimed"),
+        ));
+    }
 
-    let AddTicketReq {
-        title,
-        assign_flow_users,
-    } = new_ticket_req.into_inner();
+    let process_schema = process_flow
+        .get_schema(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let access = process_flow
+        .get_eligible_access(&mut conn, &user)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let acting_user_id = match access {
+        TicketFlowAccess::Denied => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_assign_to_this_flow"),
+            ));
+        }
+        TicketFlowAccess::Conflicted => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.conflict_of_interest"),
+            ));
+        }
+        TicketFlowAccess::OnBehalfOf(user_id) => user_id,
+        TicketFlowAccess::Direct | TicketFlowAccess::Open => user.id.clone(),
+    };
 
+    if let TicketSchemaFlowValue::Review(review_schema) = &process_schema.module {
+        if review_schema.vote.is_some() {
+            return Err(AppError::bad_request(
+                i18n.t("ticket.error.flow_cannot_be_claimed"),
+            ));
+        }
+    }
+
+    let claimed_message = i18n.t("ticket.error.flow_already_claimed");
     conn.transaction(|conn| {
         async move {
-            let ticket = Ticket::create(conn, &schema, &title).await?;
-
-            let _ = ticket.fill_flows(conn, &flows, assign_flow_users).await?;
+            let mut process_flow = process_flow.lock(conn).await?;
+            if process_flow.finished || process_flow.user_id.is_some() {
+                return Err(AppError::bad_request(claimed_message));
+            }
+            process_flow.user_id = Some(acting_user_id);
+            process_flow.claimed = true;
+            let _ = process_flow.bump_version(conn).await?;
+            let _ = process_flow.save(conn).await?;
+            let _ = TicketHistory::create(
+                conn,
+                ticket.id,
+                Some(&process_flow),
+                Some(&user),
+                TicketHistoryAction::Claim,
+                None,
+            )
+            .await?;
 
-            Ok::<_, diesel::result::Error>(EmptyResponse)
+            Ok::<_, AppError>(())
         }
         .scope_boxed()
     })
-    .await
-    .map_err(|err| AppError::internal(err.to_string()))?;
-    Ok(EmptyResponse)
-}
+    .await?;
 
-#[derive(FromForm)]
-struct UploadFormField<'r> {
-    file: TempFile<'r>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(untagged)]
-enum UploadResult {
-    File(TicketFormFile),
-    Image(TicketFormImage),
+    Ok(EmptyResponse)
 }
 
-#[post(
-    "/ticket/schemas/<schema_id>/form/<form_id>/field/<field_id>/upload",
-    data = "<upload_file_req>"
-)]
-async fn upload_file_in_form_field<'a>(
+#[post("/ticket/tickets/<ticket_id>/release")]
+async fn release_ticket_flow<'a>(
     mut conn: DbConn,
     auth: AuthGuard,
     i18n: I18n<'a>,
-    data_folder: &State<DataFolder>,
-    schema_id: i32,
-    form_id: i32,
-    field_id: i32,
-    upload_file_req: Form<UploadFormField<'_>>,
-) -> JsonResult<UploadResult> {
+    ticket_id: i32,
+) -> EmptyResult {
     let AuthGuard { user, .. } = auth;
-
-    let UploadFormField { file } = upload_file_req.into_inner();
-
-    let form = TicketSchemaForm::find_with_field(&mut conn, form_id, field_id)
+    let ticket = Ticket::find(&mut conn, ticket_id)
         .await
         .map_err(|err| AppError::not_found(err.to_string()))?;
-    let schema_flow = TicketSchemaFlow::find(&mut conn, form.form.ticket_schema_flow_id)
+
+    let process_flow = ticket
+        .get_process_flow(&mut conn)
         .await
         .map_err(|err| AppError::not_found(err.to_string()))?;
-    if schema_flow.ticket_schema_id != schema_id {
-        return Err(AppError::bad_request("Invalid schema".to_owned()));
-    }
-    let schema_target = Target::find(&mut conn, schema_flow.operator_id)
-        .await
-        .map_err(|err| AppError::internal(err.to_string()))?;
-    let is_schema_user = Target::is_user_in_targets(&mut conn, &user, &vec![schema_target])
-        .await
-        .map_err(|err| AppError::forbidden(err.to_string()))?;
-    if !is_schema_user {
-        return Err(AppError::forbidden(
-            i18n.t("ticket.error.not_assign_to_this_schema"),
+    if !process_flow.claimed {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.flow_not_claimed"),
         ));
     }
 
-    if form.field.is_file_define() {
-        return form
-            .upload_file(&mut conn, data_folder, &i18n, file)
-            .await
-            .map(|mut f: TicketFormFile| {
-                f.path = String::new();
-                Json(UploadResult::File(f))
-            })
-            .map_err(|err| AppError::bad_request(err.to_string()));
-    }
+    let access = process_flow
+        .get_access(&mut conn, &user)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let acting_user_id = match access {
+        TicketFlowAccess::Direct => user.id.clone(),
+        TicketFlowAccess::OnBehalfOf(user_id) => user_id,
+        _ => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_assign_to_this_flow"),
+            ))
+        }
+    };
 
-    if form.field.is_image_define() {
-        return form
-            .upload_image(&mut conn, data_folder, &i18n, file)
-            .await
-            .map(|mut f| {
+    let assign_message = i18n.t("ticket.error.not_assign_to_this_flow");
+    conn.transaction(|conn| {
+        async move {
+            let mut process_flow = process_flow.lock(conn).await?;
+            if process_flow.finished
+                || !process_flow.claimed
+                || process_flow.user_id.as_ref() != Some(&acting_user_id)
+            {
+                return Err(AppError::forbidden(assign_message));
+            }
+            process_flow.user_id = None;
+            process_flow.claimed = false;
+            let _ = process_flow.bump_version(conn).await?;
+            let _ = process_flow.save(conn).await?;
+            let _ = TicketFlowAssignee::delete_flow_assignee(conn, &process_flow, &acting_user_id)
+                .await?;
+            let _ = TicketHistory::create(
+                conn,
+                ticket.id,
+                Some(&process_flow),
+                Some(&user),
+                TicketHistoryAction::Release,
+                None,
+            )
+            .await?;
+
+            Ok::<_, AppError>(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+
+    Ok(EmptyResponse)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketConflictReq {
+    pub reason: Option<String>,
+}
+
+#[post("/ticket/tickets/<ticket_id>/conflicts", data = "<conflict_req>")]
+async fn declare_conflict_in_ticket<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    ticket_id: i32,
+    conflict_req: Json<TicketConflictReq>,
+) -> JsonResult<TicketConflict> {
+    let AuthGuard { user, .. } = auth;
+    let ticket = Ticket::find(&mut conn, ticket_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match ticket.is_user(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_join_to_this_ticket"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+    let schema = TicketSchema::find(&mut conn, ticket.ticket_schema_id)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let conflict = TicketConflict::create(
+        &mut conn,
+        &schema,
+        Some(ticket.id),
+        &user,
+        None,
+        conflict_req.into_inner().reason,
+    )
+    .await
+    .map_err(|err| AppError::internal(err.to_string()))?;
+    Ok(Json(conflict))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SchemaConflictReq {
+    pub field_key: String,
+    pub field_value: String,
+    pub reason: Option<String>,
+}
+
+#[post("/ticket/schemas/<schema_id>/conflicts", data = "<conflict_req>")]
+async fn declare_conflict_in_schema<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+    conflict_req: Json<SchemaConflictReq>,
+) -> JsonResult<TicketConflict> {
+    let AuthGuard { user, .. } = auth;
+    let schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match schema.is_probably_user(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_assign_to_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let SchemaConflictReq {
+        field_key,
+        field_value,
+        reason,
+    } = conflict_req.into_inner();
+    if field_key.trim().is_empty() || field_value.trim().is_empty() {
+        return Err(AppError::bad_request("Invalid conflict field".to_owned()));
+    }
+
+    let conflict = TicketConflict::create(
+        &mut conn,
+        &schema,
+        None,
+        &user,
+        Some((field_key, field_value)),
+        reason,
+    )
+    .await
+    .map_err(|err| AppError::internal(err.to_string()))?;
+    Ok(Json(conflict))
+}
+
+#[get("/ticket/conflicts")]
+async fn all_conflicts(mut conn: DbConn, auth: AuthGuard) -> JsonResult<Vec<TicketConflict>> {
+    let AuthGuard { user, .. } = auth;
+    let conflicts = TicketConflict::get_user_conflicts(&mut conn, &user)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    Ok(Json(conflicts))
+}
+
+#[delete("/ticket/conflicts/<conflict_id>")]
+async fn delete_conflict<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    conflict_id: i32,
+) -> EmptyResult {
+    let AuthGuard { user, .. } = auth;
+    let conflict = TicketConflict::find(&mut conn, conflict_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    if conflict.user_id != user.id {
+        return Err(AppError::forbidden(
+            i18n.t("ticket.error.not_owner_of_this_conflict"),
+        ));
+    }
+
+    conflict
+        .delete(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    Ok(EmptyResponse)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct AddTicketReq {
+    pub title: String,
+    pub assign_flow_users: HashMap<i32, String>,
+}
+
+#[post("/ticket/schemas/<schema_id>/tickets", data = "<new_ticket_req>")]
+async fn add_ticket_for_schema<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+    new_ticket_req: Json<AddTicketReq>,
+) -> EmptyResult {
+    let AuthGuard { user, .. } = auth;
+    let schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+
+    match schema.is_probably_join_user(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_join_to_this_ticket"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let flows = schema
+        .get_flows(&mut conn)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+
+    let AddTicketReq {
+        title,
+        assign_flow_users,
+    } = new_ticket_req.into_inner();
+    let title = title.trim().to_owned();
+    if title.is_empty() || title.chars().count() > Ticket::TITLE_MAX_LENGTH {
+        return Err(AppError::bad_request(i18n.tf(
+            "ticket.rules.text_too_long",
+            &[("field", "title".to_owned())],
+        )));
+    }
+
+    conn.transaction(|conn| {
+        async move {
+            let ticket = Ticket::create(conn, &schema, &title).await?;
+
+            let _ = ticket.fill_flows(conn, &flows, assign_flow_users).await?;
+            ticket.activate_process_flow(conn).await?;
+
+            Ok::<_, diesel::result::Error>(EmptyResponse)
+        }
+        .scope_boxed()
+    })
+    .await
+    .map_err(|err| AppError::internal(err.to_string()))?;
+    Ok(EmptyResponse)
+}
+
+#[derive(FromForm)]
+struct UploadFormField<'r> {
+    file: TempFile<'r>,
+}
+
+struct FormFieldPath {
+    field_id: i32,
+    sub_key: Option<String>,
+}
+
+impl<'a> FromParam<'a> for FormFieldPath {
+    type Error = &'a str;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        let (field_id, sub_key) = match param.split_once('.') {
+            Some((field_id, sub_key)) => (field_id, Some(sub_key.to_owned())),
+            None => (param, None),
+        };
+        let field_id = field_id.parse::<i32>().map_err(|_| param)?;
+        Ok(FormFieldPath { field_id, sub_key })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+enum UploadResult {
+    File(TicketFormFile),
+    Image(TicketFormImage),
+}
+
+#[post(
+    "/ticket/schemas/<schema_id>/form/<form_id>/field/<field_path>/upload",
+    data = "<upload_file_req>"
+)]
+async fn upload_file_in_form_field<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    data_folder: &State<DataFolder>,
+    schema_id: i32,
+    form_id: i32,
+    field_path: FormFieldPath,
+    upload_file_req: Form<UploadFormField<'_>>,
+) -> JsonResult<UploadResult> {
+    let AuthGuard { user, .. } = auth;
+
+    let UploadFormField { file } = upload_file_req.into_inner();
+    let FormFieldPath { field_id, sub_key } = field_path;
+
+    let mut form = TicketSchemaForm::find_with_field(&mut conn, form_id, field_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    if let Some(sub_key) = sub_key {
+        form.field = form
+            .field
+            .find_group_field(&sub_key)
+            .ok_or(AppError::not_found("Not Found".to_owned()))?;
+    }
+    let schema_flow = TicketSchemaFlow::find(&mut conn, form.form.ticket_schema_flow_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    if schema_flow.ticket_schema_id != schema_id {
+        return Err(AppError::bad_request("Invalid schema".to_owned()));
+    }
+    let schema_target = Target::find(&mut conn, schema_flow.operator_id)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let is_schema_user = Target::is_user_in_targets(&mut conn, &user, &vec![schema_target])
+        .await
+        .map_err(|err| AppError::forbidden(err.to_string()))?;
+    if !is_schema_user {
+        return Err(AppError::forbidden(
+            i18n.t("ticket.error.not_assign_to_this_schema"),
+        ));
+    }
+
+    if form.field.is_file_define() {
+        return form
+            .upload_file(&mut conn, data_folder, &i18n, file)
+            .await
+            .map(|mut f: TicketFormFile| {
+                f.path = String::new();
+                Json(UploadResult::File(f))
+            })
+            .map_err(|err| AppError::bad_request(err.to_string()));
+    }
+
+    if form.field.is_image_define() {
+        return form
+            .upload_image(&mut conn, data_folder, &i18n, file)
+            .await
+            .map(|mut f| {
                 f.path = String::new();
                 Json(UploadResult::Image(f))
             })
@@ -529,17 +1134,18 @@ async fn get_public_image_content(
         .map_err(|err| AppError::not_found(err.to_string()))
 }
 
-#[get("/ticket/schemas/<schema_id>/form/<form_id>/field/<field_id>/<file_id>")]
+#[get("/ticket/schemas/<schema_id>/form/<form_id>/field/<field_path>/<file_id>")]
 async fn get_field_file_content<'a>(
     mut conn: DbConn,
     auth: AuthGuard,
     i18n: I18n<'a>,
     schema_id: i32,
     form_id: i32,
-    field_id: i32,
+    field_path: FormFieldPath,
     file_id: String,
 ) -> ApiResult<NamedFile> {
     let AuthGuard { user, .. } = auth;
+    let FormFieldPath { field_id, sub_key } = field_path;
 
     let schema = TicketSchema::find(&mut conn, schema_id)
         .await
@@ -555,9 +1161,15 @@ async fn get_field_file_content<'a>(
         _ => (),
     }
 
-    let form = TicketSchemaForm::find_with_field(&mut conn, form_id, field_id)
+    let mut form = TicketSchemaForm::find_with_field(&mut conn, form_id, field_id)
         .await
         .map_err(|err| AppError::not_found(err.to_string()))?;
+    if let Some(sub_key) = sub_key {
+        form.field = form
+            .field
+            .find_group_field(&sub_key)
+            .ok_or(AppError::not_found("Not Found".to_owned()))?;
+    }
 
     let mut id = file_id.clone();
     if file_id.contains('.') {
@@ -633,15 +1245,28 @@ struct NewTicketSchemaReq {
     pub title_en: String,
     pub description_zh: String,
     pub description_en: String,
+    #[serde(default)]
+    pub dates: Option<TicketSchemaDates>,
 }
 
 #[post("/ticket/admin/schemas", data = "<new_schema_req>")]
-async fn add_managed_schema_in_admin(
+async fn add_managed_schema_in_admin<'a>(
     mut conn: DbConn,
     auth: AuthGuard,
+    i18n: I18n<'a>,
     new_schema_req: Json<NewTicketSchemaReq>,
 ) -> EmptyResult {
     let AuthGuard { user, project, .. } = auth;
+    if new_schema_req
+        .dates
+        .as_ref()
+        .is_some_and(|dates| !dates.is_valid())
+    {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.invalid_schema_dates"),
+        ));
+    }
+
     conn.transaction(|conn| {
         async move {
             let schema = TicketSchema::create(
@@ -651,6 +1276,7 @@ async fn add_managed_schema_in_admin(
                 new_schema_req.description_zh.clone(),
                 new_schema_req.description_en.clone(),
                 project.id,
+                new_schema_req.dates.clone(),
             )
             .await?;
 
@@ -666,13 +1292,13 @@ async fn add_managed_schema_in_admin(
     Ok(EmptyResponse)
 }
 
-#[post("/ticket/admin/schemas/<schema_id>/flows", data = "<new_flow_req>")]
-async fn add_flow_to_schema_in_admin<'a>(
+#[put("/ticket/admin/schemas/<schema_id>/dates", data = "<dates_req>")]
+async fn update_schema_dates_in_admin<'a>(
     mut conn: DbConn,
     auth: AuthGuard,
     i18n: I18n<'a>,
     schema_id: i32,
-    new_flow_req: Json<TicketSchemaFlowItem>,
+    dates_req: Json<TicketSchemaDates>,
 ) -> EmptyResult {
     let AuthGuard { user, .. } = auth;
     let mut schema = TicketSchema::find(&mut conn, schema_id)
@@ -688,51 +1314,932 @@ async fn add_flow_to_schema_in_admin<'a>(
         _ => (),
     };
 
-    conn.transaction(|conn| {
-        async move {
-            schema.updated_at = chrono::Utc::now().naive_utc();
-            let _ = schema.save(conn).await?;
-            let flow = schema
-                .add_flow(
-                    conn,
-                    new_flow_req.schema.name_zh.clone(),
-                    new_flow_req.schema.name_en.clone(),
-                )
-                .await?;
+    let dates = dates_req.into_inner();
+    if !dates.is_valid() {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.invalid_schema_dates"),
+        ));
+    }
 
-            match new_flow_req.into_inner().module {
-                TicketSchemaFlowValue::Form(form_schema) => {
-                    let schema_form =
-                        TicketSchemaForm::create(conn, &flow, form_schema.form.expired_at).await?;
-                    let fields = form_schema
-                        .fields
-                        .into_iter()
-                        .map(|field| FormSchemaField {
-                            name_zh: field.name_zh,
-                            description_zh: field.description_zh,
-                            name_en: field.name_en,
-                            description_en: field.description_en,
-                            key: field.key,
-                            define: field.define,
-                            required: field.required,
-                            editable: field.editable,
-                        })
-                        .collect::<Vec<_>>();
-                    schema_form.add_fields(conn, fields).await?;
-                }
+    let flows = schema
+        .get_detail_flows(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let removes_referenced = flows.iter().any(|flow| match &flow.module {
+        TicketSchemaFlowValue::Form(form_schema) => form_schema.fields.iter().any(|field| {
+            field
+                .define
+                .schema_date_keys()
+                .into_iter()
+                .any(|key| !dates.0.contains_key(key))
+        }),
+        TicketSchemaFlowValue::Review(_) => false,
+    });
+    if removes_referenced {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.schema_date_in_use"),
+        ));
+    }
+
+    schema.dates = Some(dates);
+    schema.updated_at = chrono::Utc::now().naive_utc();
+    schema
+        .save(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    Ok(EmptyResponse)
+}
+
+#[post("/ticket/admin/schemas/<schema_id>/flows", data = "<new_flow_req>")]
+async fn add_flow_to_schema_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+    new_flow_req: Json<TicketSchemaFlowItem>,
+) -> EmptyResult {
+    let AuthGuard { user, .. } = auth;
+    let mut schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    };
+
+    if let TicketSchemaFlowValue::Review(TicketSchemaReview {
+        rubric: Some(rubric),
+        ..
+    }) = &new_flow_req.module
+    {
+        if !rubric.is_valid() {
+            return Err(AppError::bad_request(i18n.t("ticket.error.invalid_rubric")));
+        }
+    }
+
+    if let TicketSchemaFlowValue::Form(form_schema) = &new_flow_req.module {
+        let schema_dates = schema.dates.clone().unwrap_or_default();
+        if !form_schema.fields.iter().all(|field| {
+            field.define.is_valid()
+                && field
+                    .define
+                    .schema_date_keys()
+                    .into_iter()
+                    .all(|key| schema_dates.0.contains_key(key))
+        }) {
+            return Err(AppError::bad_request(i18n.t("ticket.error.invalid_field")));
+        }
+        if let Some(rules) = &form_schema.form.rules {
+            let field_keys = form_schema
+                .fields
+                .iter()
+                .map(|field| field.key.clone())
+                .collect::<Vec<_>>();
+            if !rules.is_valid(&field_keys) {
+                return Err(AppError::bad_request(
+                    i18n.t("ticket.error.invalid_form_rules"),
+                ));
+            }
+        }
+    }
+
+    if new_flow_req
+        .schema
+        .due_hours
+        .is_some_and(|due_hours| due_hours <= 0)
+    {
+        return Err(AppError::bad_request("Invalid due hours".to_owned()));
+    }
+
+    conn.transaction(|conn| {
+        async move {
+            schema.updated_at = chrono::Utc::now().naive_utc();
+            let _ = schema.save(conn).await?;
+            let flow = schema
+                .add_flow(
+                    conn,
+                    new_flow_req.schema.name_zh.clone(),
+                    new_flow_req.schema.name_en.clone(),
+                    new_flow_req.schema.assignment.clone(),
+                    new_flow_req.schema.due_hours,
+                    new_flow_req.schema.condition.clone(),
+                )
+                .await?;
+
+            match new_flow_req.into_inner().module {
+                TicketSchemaFlowValue::Form(form_schema) => {
+                    let schema_form = TicketSchemaForm::create(
+                        conn,
+                        &flow,
+                        form_schema.form.expired_at,
+                        form_schema.form.rules,
+                    )
+                    .await?;
+                    let fields = form_schema
+                        .fields
+                        .into_iter()
+                        .map(|field| FormSchemaField {
+                            name_zh: field.name_zh,
+                            description_zh: field.description_zh,
+                            name_en: field.name_en,
+                            description_en: field.description_en,
+                            key: field.key,
+                            define: field.define,
+                            required: field.required,
+                            editable: field.editable,
+                            identifying: field.identifying,
+                        })
+                        .collect::<Vec<_>>();
+                    schema_form.add_fields(conn, fields).await?;
+                }
                 TicketSchemaFlowValue::Review(review_schema) => {
-                    TicketSchemaReview::create(conn, &flow, review_schema.restarted).await?;
+                    TicketSchemaReview::create(
+                        conn,
+                        &flow,
+                        review_schema.restarted,
+                        review_schema.vote,
+                        review_schema.rubric,
+                        review_schema.anonymous,
+                        review_schema.rules,
+                    )
+                    .await?;
+                }
+            }
+
+            Ok::<_, diesel::result::Error>(())
+        }
+        .scope_boxed()
+    })
+    .await
+    .map_err(|err| AppError::internal(err.to_string()))?;
+
+    Ok(EmptyResponse)
+}
+
+#[post("/ticket/admin/schemas/<schema_id>/flows/<flow_id>/rebalance")]
+async fn rebalance_flow_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+    flow_id: i32,
+) -> JsonResult<Vec<TicketFlowAssignment>> {
+    let AuthGuard { user, .. } = auth;
+    let schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let schema_flow = TicketSchemaFlow::find(&mut conn, flow_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    if schema_flow.ticket_schema_id != schema.id {
+        return Err(AppError::not_found("Flow not found".to_owned()));
+    }
+    if schema_flow.assignment.is_none() {
+        return Err(AppError::bad_request(
+            "Flow has no assignment strategy".to_owned(),
+        ));
+    }
+
+    let tickets = schema
+        .get_tickets(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let assignments = conn
+        .transaction(|conn| {
+            async move {
+                let mut flows = vec![];
+                for ticket in tickets.iter().filter(|ticket| !ticket.finished) {
+                    let mut flow = match ticket.get_process_flow(conn).await {
+                        Ok(flow) => flow,
+                        Err(diesel::result::Error::NotFound) => continue,
+                        Err(err) => return Err(err),
+                    };
+                    if flow.ticket_schema_flow_id != schema_flow.id {
+                        continue;
+                    }
+                    let reviews = TicketReview::get_flow_reviews(conn, &flow).await?;
+                    if !reviews.is_empty() {
+                        continue;
+                    }
+                    let assignees = TicketFlowAssignee::get_flow_assignees(conn, &flow).await?;
+                    if flow.user_id.is_some() && assignees.is_empty() {
+                        continue;
+                    }
+                    let _ = TicketFlowAssignee::delete_flow_assignees(conn, &flow).await?;
+                    flow.user_id = None;
+                    flow.claimed = false;
+                    let _ = flow.save(conn).await?;
+                    flows.push(flow);
+                }
+
+                let mut result = vec![];
+                for mut flow in flows.into_iter() {
+                    let users = flow.assign(conn).await?;
+                    result.push(TicketFlowAssignment {
+                        ticket_id: flow.ticket_id,
+                        ticket_flow_id: flow.id,
+                        users,
+                    });
+                }
+
+                Ok::<_, diesel::result::Error>(result)
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    Ok(Json(assignments))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReassignTicketFlowReq {
+    pub user_id: String,
+    pub reason: Option<String>,
+}
+
+#[post("/ticket/admin/tickets/<ticket_id>/reassign", data = "<reassign_req>")]
+async fn reassign_ticket_flow_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    ticket_id: i32,
+    reassign_req: Json<ReassignTicketFlowReq>,
+) -> EmptyResult {
+    let AuthGuard { user, .. } = auth;
+    let ticket = Ticket::find(&mut conn, ticket_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    let schema = TicketSchema::find(&mut conn, ticket.ticket_schema_id)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let process_flow = ticket
+        .get_process_flow(&mut conn)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    let process_schema = process_flow
+        .get_schema(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    if let TicketSchemaFlowValue::Review(review_schema) = &process_schema.module {
+        if review_schema.vote.is_some() {
+            return Err(AppError::bad_request(
+                i18n.t("ticket.error.flow_cannot_be_reassigned"),
+            ));
+        }
+    }
+    let schema_flow = process_schema.schema;
+
+    let ReassignTicketFlowReq { user_id, reason } = reassign_req.into_inner();
+    let candidates = schema_flow
+        .get_probably_assign_users(&mut conn, Some(&ticket))
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    if !candidates.iter().any(|candidate| candidate.id == user_id) {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.user_not_in_flow_target"),
+        ));
+    }
+
+    let stale_message = i18n.t("ticket.error.flow_was_modified");
+    conn.transaction(|conn| {
+        async move {
+            let mut process_flow = process_flow.lock(conn).await?;
+            if process_flow.finished {
+                return Err(AppError::conflict(
+                    stale_message,
+                    serde_json::to_value(&process_flow).unwrap_or_default(),
+                ));
+            }
+            let from = process_flow.user_id.clone();
+            process_flow.user_id = Some(user_id.clone());
+            process_flow.claimed = false;
+            let _ = TicketFlowAssignee::delete_flow_assignees(conn, &process_flow).await?;
+            let _ = process_flow.bump_version(conn).await?;
+            let _ = process_flow.save(conn).await?;
+            let _ = TicketHistory::create(
+                conn,
+                ticket.id,
+                Some(&process_flow),
+                Some(&user),
+                TicketHistoryAction::Reassign,
+                Some(serde_json::json!({
+                    "from": from,
+                    "to": user_id,
+                    "reason": reason,
+                })),
+            )
+            .await?;
+
+            Ok::<_, AppError>(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+
+    Ok(EmptyResponse)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EditTicketFlowAnswerReq {
+    pub data: Map<String, Value>,
+    pub reason: String,
+    pub version: i32,
+}
+
+#[put(
+    "/ticket/admin/tickets/<ticket_id>/flows/<flow_id>/answers",
+    data = "<edit_req>"
+)]
+async fn edit_ticket_flow_answer_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    ticket_id: i32,
+    flow_id: i32,
+    edit_req: Json<EditTicketFlowAnswerReq>,
+) -> EmptyResult {
+    let AuthGuard { user, .. } = auth;
+    let ticket = Ticket::find(&mut conn, ticket_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    let schema = TicketSchema::find(&mut conn, ticket.ticket_schema_id)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let EditTicketFlowAnswerReq {
+        data,
+        reason,
+        version,
+    } = edit_req.into_inner();
+    if reason.trim().is_empty() {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.override_reason_required"),
+        ));
+    }
+
+    let mut flow = TicketFlow::find(&mut conn, flow_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    if flow.ticket_id != ticket.id {
+        return Err(AppError::not_found("Flow not found".to_owned()));
+    }
+    if !flow.finished {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.flow_not_finished"),
+        ));
+    }
+
+    let form_schema = match flow
+        .get_schema(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?
+        .module
+    {
+        TicketSchemaFlowValue::Form(form_schema) => form_schema,
+        _ => return Err(AppError::bad_request("Invalid request".to_owned())),
+    };
+
+    let previous = TicketFormAnswer::find_by_flow(&mut conn, &flow)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?
+        .map(|answer| answer.value);
+    let mut value = match &previous {
+        Some(Value::Object(previous)) => previous.clone(),
+        _ => Map::new(),
+    };
+    let mut merged_data = value.clone();
+    merged_data.extend(data.clone());
+
+    let stale_message = i18n.t("ticket.error.flow_was_modified");
+    let mut normalized_data = match form_schema
+        .validate_and_normalize(&mut conn, &i18n, &merged_data)
+        .await
+    {
+        Ok(normalized_data) => normalized_data,
+        Err(fields) => return Err(AppError::bad_request_with_fields(i18n, fields)),
+    };
+    let mut changes = Map::new();
+    for (key, user_value) in data.iter() {
+        match normalized_data.remove(key) {
+            Some(new_value) => {
+                changes.insert(key.clone(), new_value);
+            }
+            None => {
+                let is_editable = form_schema
+                    .fields
+                    .iter()
+                    .any(|field| field.key == *key && field.editable);
+                if user_value.is_null() && is_editable {
+                    value.remove(key);
+                }
+            }
+        }
+    }
+
+    conn.transaction(|conn| {
+        async move {
+            let current_flow = flow.lock(conn).await?;
+            if current_flow.version != version {
+                return Err(AppError::conflict(
+                    stale_message,
+                    serde_json::to_value(&current_flow).unwrap_or_default(),
+                ));
+            }
+            if let Err(fields) = form_schema
+                .reserve_capacity(conn, &i18n, ticket.id, &mut changes)
+                .await?
+            {
+                return Err(AppError::bad_request_with_fields(i18n, fields));
+            }
+            value.extend(changes);
+            let _ = flow.bump_version(conn).await?;
+            let _ = TicketFormAnswer::save_or_create(conn, &flow, &form_schema, value).await?;
+            form_schema.promote_waitlist(conn).await?;
+            let _ = TicketHistory::create(
+                conn,
+                ticket.id,
+                Some(&flow),
+                Some(&user),
+                TicketHistoryAction::Edit,
+                Some(serde_json::json!({
+                    "previous": previous,
+                    "reason": reason,
+                })),
+            )
+            .await?;
+
+            Ok::<_, AppError>(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+
+    Ok(EmptyResponse)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OverrideTicketFlowReq {
+    pub reason: String,
+    pub version: i32,
+}
+
+async fn override_ticket_flow<'a>(
+    mut conn: DbConn,
+    user: User,
+    i18n: I18n<'a>,
+    ticket_id: i32,
+    flow_id: i32,
+    override_req: OverrideTicketFlowReq,
+    skipped: bool,
+) -> EmptyResult {
+    let ticket = Ticket::find(&mut conn, ticket_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    let schema = TicketSchema::find(&mut conn, ticket.ticket_schema_id)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let OverrideTicketFlowReq { reason, version } = override_req;
+    if reason.trim().is_empty() {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.override_reason_required"),
+        ));
+    }
+
+    let flow = TicketFlow::find(&mut conn, flow_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    if flow.ticket_id != ticket.id {
+        return Err(AppError::not_found("Flow not found".to_owned()));
+    }
+
+    let not_in_progress_message = i18n.t("ticket.error.flow_not_in_progress");
+    let stale_message = i18n.t("ticket.error.flow_was_modified");
+    conn.transaction(|conn| {
+        async move {
+            let mut process_flow = flow.lock(conn).await?;
+            let current_flow = ticket.get_process_flow(conn).await;
+            if process_flow.finished
+                || process_flow.skipped
+                || !current_flow.is_ok_and(|current_flow| current_flow.id == process_flow.id)
+            {
+                return Err(AppError::bad_request(not_in_progress_message));
+            }
+            if process_flow.version != version {
+                return Err(AppError::conflict(
+                    stale_message,
+                    serde_json::to_value(&process_flow).unwrap_or_default(),
+                ));
+            }
+            let latest_flow = ticket.get_latest_flow(conn).await?;
+            let _ = process_flow.bump_version(conn).await?;
+            process_flow.finished = true;
+            process_flow.skipped = skipped;
+            let _ = process_flow.save(conn).await?;
+            let _ = TicketHistory::create(
+                conn,
+                ticket.id,
+                Some(&process_flow),
+                Some(&user),
+                match skipped {
+                    true => TicketHistoryAction::Skip,
+                    false => TicketHistoryAction::ForceComplete,
+                },
+                Some(serde_json::json!({ "reason": reason })),
+            )
+            .await?;
+
+            if latest_flow.id == process_flow.id {
+                let _ = ticket.set_finish(conn, true).await?;
+            }
+            ticket.activate_process_flow(conn).await?;
+
+            Ok::<_, AppError>(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+
+    Ok(EmptyResponse)
+}
+
+#[post(
+    "/ticket/admin/tickets/<ticket_id>/flows/<flow_id>/complete",
+    data = "<override_req>"
+)]
+async fn complete_ticket_flow_in_admin<'a>(
+    conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    ticket_id: i32,
+    flow_id: i32,
+    override_req: Json<OverrideTicketFlowReq>,
+) -> EmptyResult {
+    let AuthGuard { user, .. } = auth;
+    override_ticket_flow(
+        conn,
+        user,
+        i18n,
+        ticket_id,
+        flow_id,
+        override_req.into_inner(),
+        false,
+    )
+    .await
+}
+
+#[post(
+    "/ticket/admin/tickets/<ticket_id>/flows/<flow_id>/skip",
+    data = "<override_req>"
+)]
+async fn skip_ticket_flow_in_admin<'a>(
+    conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    ticket_id: i32,
+    flow_id: i32,
+    override_req: Json<OverrideTicketFlowReq>,
+) -> EmptyResult {
+    let AuthGuard { user, .. } = auth;
+    override_ticket_flow(
+        conn,
+        user,
+        i18n,
+        ticket_id,
+        flow_id,
+        override_req.into_inner(),
+        true,
+    )
+    .await
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct AddBulkTicketRow {
+    pub title: String,
+    #[serde(default)]
+    pub assignees: HashMap<i32, String>,
+    #[serde(default)]
+    pub answers: serde_json::Map<String, Value>,
+}
+
+fn parse_bulk_ticket_csv(
+    text: &str,
+    form_schema: Option<&FormSchema>,
+) -> Option<Vec<AddBulkTicketRow>> {
+    let mut rows = csv::parse(text)?.into_iter();
+    let header = match rows.next() {
+        Some(header) => header
+            .into_iter()
+            .map(|column| column.trim().to_owned())
+            .collect::<Vec<_>>(),
+        None => return Some(vec![]),
+    };
+
+    let rows = rows
+        .map(|row| {
+            let mut result = AddBulkTicketRow {
+                title: String::new(),
+                assignees: HashMap::new(),
+                answers: serde_json::Map::new(),
+            };
+            for (column, cell) in header.iter().zip(row) {
+                if cell.trim().is_empty() {
+                    continue;
+                }
+                if column == "title" {
+                    result.title = cell;
+                } else if let Some(flow_id) = column
+                    .strip_prefix("assignee:")
+                    .and_then(|flow_id| flow_id.trim().parse::<i32>().ok())
+                {
+                    result.assignees.insert(flow_id, cell);
+                } else {
+                    let value = form_schema
+                        .and_then(|form_schema| {
+                            form_schema.fields.iter().find(|field| field.key == *column)
+                        })
+                        .map_or(Value::String(cell.clone()), |field| {
+                            field.define.parse_text(&cell)
+                        });
+                    result.answers.insert(column.clone(), value);
+                }
+            }
+            result
+        })
+        .collect();
+    Some(rows)
+}
+
+async fn get_bulk_ticket_schema<'a>(
+    conn: &mut DbConn,
+    user: &User,
+    i18n: &I18n<'a>,
+    schema_id: i32,
+) -> Result<(TicketSchema, Option<FormSchema>), AppError> {
+    let schema = TicketSchema::find(conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match schema.is_manager(conn, user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let first_form_schema = schema
+        .get_detail_flows(conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?
+        .into_iter()
+        .next()
+        .and_then(|flow| match flow.module {
+            TicketSchemaFlowValue::Form(mut form_schema) => {
+                if let Some(dates) = &schema.dates {
+                    form_schema.resolve_schema_dates(dates);
                 }
+                Some(form_schema)
             }
+            TicketSchemaFlowValue::Review(_) => None,
+        });
+
+    Ok((schema, first_form_schema))
+}
+
+async fn add_bulk_tickets_for_schema<'a>(
+    mut conn: DbConn,
+    i18n: I18n<'a>,
+    schema: TicketSchema,
+    first_form_schema: Option<FormSchema>,
+    rows: Vec<AddBulkTicketRow>,
+) -> JsonResult<Vec<Ticket>> {
+    if rows.is_empty() {
+        return Err(AppError::bad_request("Invalid request".to_owned()));
+    }
+
+    let flows = schema
+        .get_flows(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let mut records = vec![];
+    let mut errors = serde_json::Map::new();
+    for (index, row) in rows.into_iter().enumerate() {
+        let mut row_errors = serde_json::Map::new();
+
+        let title = row.title.trim().to_owned();
+        if title.is_empty() {
+            row_errors.insert(
+                "title".to_owned(),
+                Value::String(i18n.tf("ticket.rules.required", &[("field", "title".to_owned())])),
+            );
+        } else if title.chars().count() > Ticket::TITLE_MAX_LENGTH {
+            row_errors.insert(
+                "title".to_owned(),
+                Value::String(i18n.tf(
+                    "ticket.rules.text_too_long",
+                    &[("field", "title".to_owned())],
+                )),
+            );
+        }
+
+        let mut assign_flow_users = HashMap::new();
+        for (flow_id, email) in row.assignees.into_iter() {
+            let key = format!("assignees.{}", flow_id);
+            if !flows.iter().any(|flow| flow.id == flow_id) {
+                row_errors.insert(
+                    key.clone(),
+                    Value::String(i18n.tf("ticket.rules.unknown", &[("field", key)])),
+                );
+                continue;
+            }
+            match UserEmail::get_user(
+                &mut conn,
+                schema.project_id.clone(),
+                email.trim().to_owned(),
+            )
+            .await
+            {
+                Ok(assignee) => {
+                    assign_flow_users.insert(flow_id, assignee.id);
+                }
+                Err(_) => {
+                    row_errors.insert(
+                        key,
+                        Value::String(i18n.tf("ticket.rules.unknown_user", &[("field", email)])),
+                    );
+                }
+            }
+        }
+
+        let answers = match (&first_form_schema, row.answers.is_empty()) {
+            (_, true) => None,
+            (Some(form_schema), false) => {
+                match form_schema
+                    .validate_draft(&mut conn, &i18n, &row.answers)
+                    .await
+                {
+                    Ok(normalized_data) => Some(normalized_data),
+                    Err(fields) => {
+                        row_errors.extend(
+                            fields
+                                .into_iter()
+                                .map(|(key, value)| (format!("answers.{}", key), value)),
+                        );
+                        None
+                    }
+                }
+            }
+            (None, false) => {
+                row_errors.insert(
+                    "answers".to_owned(),
+                    Value::String(
+                        i18n.tf("ticket.rules.unknown", &[("field", "answers".to_owned())]),
+                    ),
+                );
+                None
+            }
+        };
+
+        if row_errors.is_empty() {
+            records.push((title, assign_flow_users, answers));
+        } else {
+            errors.insert(index.to_string(), Value::Object(row_errors));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::bad_request_with_fields(i18n, errors));
+    }
+
+    let tickets = conn
+        .transaction(|conn| {
+            async move {
+                let mut tickets = vec![];
+                for (title, assign_flow_users, answers) in records.into_iter() {
+                    let ticket = Ticket::create(conn, &schema, &title).await?;
+                    ticket.fill_flows(conn, &flows, assign_flow_users).await?;
+                    if let (Some(form_schema), Some(answers)) = (&first_form_schema, answers) {
+                        let first_flow = ticket.get_process_flow(conn).await?;
+                        let _ = TicketFormAnswer::save_or_create(
+                            conn,
+                            &first_flow,
+                            form_schema,
+                            answers,
+                        )
+                        .await?;
+                    }
+
+                    ticket.activate_process_flow(conn).await?;
+                    tickets.push(Ticket::find(conn, ticket.id).await?);
+                }
+
+                Ok::<_, diesel::result::Error>(tickets)
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    Ok(Json(tickets))
+}
+
+#[post(
+    "/ticket/admin/schemas/<schema_id>/tickets/bulk",
+    format = "json",
+    data = "<rows>"
+)]
+async fn add_bulk_tickets_for_schema_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+    rows: Json<Vec<AddBulkTicketRow>>,
+) -> JsonResult<Vec<Ticket>> {
+    let AuthGuard { user, .. } = auth;
+    let (schema, first_form_schema) =
+        get_bulk_ticket_schema(&mut conn, &user, &i18n, schema_id).await?;
+    add_bulk_tickets_for_schema(conn, i18n, schema, first_form_schema, rows.into_inner()).await
+}
 
-            Ok::<_, diesel::result::Error>(())
-        }
-        .scope_boxed()
-    })
-    .await
-    .map_err(|err| AppError::internal(err.to_string()))?;
+#[post(
+    "/ticket/admin/schemas/<schema_id>/tickets/bulk",
+    format = "text/csv",
+    data = "<data>"
+)]
+async fn add_bulk_tickets_from_csv_for_schema_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+    data: Data<'_>,
+) -> JsonResult<Vec<Ticket>> {
+    let AuthGuard { user, .. } = auth;
+    let (schema, first_form_schema) =
+        get_bulk_ticket_schema(&mut conn, &user, &i18n, schema_id).await?;
 
-    Ok(EmptyResponse)
+    let text = data
+        .open(2.mebibytes())
+        .into_string()
+        .await
+        .map_err(|err| AppError::bad_request(err.to_string()))?;
+    if !text.is_complete() {
+        return Err(AppError::bad_request("CSV is too large".to_owned()));
+    }
+
+    let rows = match parse_bulk_ticket_csv(&text, first_form_schema.as_ref()) {
+        Some(rows) => rows,
+        None => return Err(AppError::bad_request(i18n.t("ticket.error.invalid_csv"))),
+    };
+    add_bulk_tickets_for_schema(conn, i18n, schema, first_form_schema, rows).await
 }
 
 #[get("/ticket/admin/schemas/<schema_id>/tickets")]
@@ -741,7 +2248,7 @@ async fn all_tickets_for_schema_in_admin<'a>(
     auth: AuthGuard,
     i18n: I18n<'a>,
     schema_id: i32,
-) -> JsonResult<Vec<Ticket>> {
+) -> JsonResult<Vec<TicketWithStatus>> {
     let AuthGuard { user, .. } = auth;
     let schema = TicketSchema::find(&mut conn, schema_id)
         .await
@@ -757,6 +2264,18 @@ async fn all_tickets_for_schema_in_admin<'a>(
     }
     let tickets = schema
         .get_tickets(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?
+        .into_iter()
+        .map(|ticket| {
+            let status = match ticket.finished {
+                true => TicketStatus::Finished,
+                false => TicketStatus::InProgress,
+            };
+            (ticket, status)
+        })
+        .collect::<Vec<_>>();
+    let tickets = Ticket::with_status(&mut conn, tickets)
         .await
         .map_err(|err| AppError::internal(err.to_string()))?;
     Ok(Json(tickets))
@@ -765,7 +2284,8 @@ async fn all_tickets_for_schema_in_admin<'a>(
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ExportTicketData {
     pub fields: Vec<Value>,
-    pub data: Vec<Map<String, Value>>
+    pub data: Vec<Map<String, Value>>,
+    pub totals: Map<String, Value>,
 }
 
 #[get("/ticket/admin/schemas/<schema_id>/exports")]
@@ -796,16 +2316,24 @@ async fn export_tickets_for_schema_in_admin<'a>(
 
     let mut response = ExportTicketData {
         fields: vec![],
-        data: vec![]
+        data: vec![],
+        totals: Map::new(),
     };
 
     let schema_flows = schema.get_detail_flows(&mut conn).await.map_err(|err| AppError::internal(err.to_string()))?;
 
     response.fields.push(serde_json::json!({
         "module_type": "Base",
-        "key": "ticket_id"
+        "key": "ticket_id",
+        "value_type": "number"
     }));
 
+    let mut rankings: HashMap<i32, Vec<TicketReviewScore>> = HashMap::new();
+    let mut number_keys: Vec<(i32, String)> = vec![];
+    let mut groups: Vec<(usize, &TicketSchemaFormField)> = vec![];
+    let mut choice_fields: Vec<&TicketSchemaFormField> = vec![];
+    let mut group_sizes: HashMap<String, usize> = HashMap::new();
+
     for schema_flow in &schema_flows {
         match &schema_flow.module {
             TicketSchemaFlowValue::Form(form) => {
@@ -813,25 +2341,79 @@ async fn export_tickets_for_schema_in_admin<'a>(
                     if form_field.key == "" {
                         continue;
                     };
+                    if let FormFieldDefine::Number { .. } = form_field.define {
+                        number_keys.push((form.form.id, form_field.key.clone()));
+                    }
+                    if let FormFieldDefine::Group { .. } = form_field.define {
+                        groups.push((response.fields.len(), form_field));
+                        continue;
+                    }
                     response.fields.push(serde_json::json!({
                         "module_type": "Form",
                         "key": form_field.key,
+                        "value_type": form_field.define.value_type(),
                         "define": form_field.define,
                         "name_zh": form_field.name_zh,
                         "name_en": form_field.name_en,
                     }));
+                    if let FormFieldDefine::SingleChoice { ref other, .. }
+                    | FormFieldDefine::MultipleChoice { ref other, .. } = form_field.define
+                    {
+                        choice_fields.push(form_field);
+                        response.fields.push(serde_json::json!({
+                            "module_type": "Form",
+                            "key": format!("{}_label", form_field.key),
+                            "value_type": "string",
+                            "name_zh": form_field.name_zh,
+                            "name_en": form_field.name_en,
+                        }));
+                        if let Some(other) = other {
+                            response.fields.push(serde_json::json!({
+                                "module_type": "Form",
+                                "key": format!("{}_other", form_field.key),
+                                "value_type": "string",
+                                "name_zh": other.text_zh,
+                                "name_en": other.text_en,
+                            }));
+                        }
+                    }
                 }
             },
-            TicketSchemaFlowValue::Review(_) => {
+            TicketSchemaFlowValue::Review(review_schema) => {
                 let review_key_prefix = format!("review_{}", schema_flow.schema.order);
                 response.fields.push(serde_json::json!({
                     "module_type": "Review",
-                    "key": format!("{}_approved", review_key_prefix)
+                    "key": format!("{}_approved", review_key_prefix),
+                    "value_type": "bool"
                 }));
                 response.fields.push(serde_json::json!({
                     "module_type": "Review",
-                    "key": format!("{}_comment", review_key_prefix)
+                    "key": format!("{}_comment", review_key_prefix),
+                    "value_type": "string"
                 }));
+                if let Some(rubric) = &review_schema.rubric {
+                    for criterion in &rubric.criteria {
+                        response.fields.push(serde_json::json!({
+                            "module_type": "Review",
+                            "key": format!("{}_{}", review_key_prefix, criterion.key),
+                            "value_type": "number",
+                            "name_zh": criterion.name_zh,
+                            "name_en": criterion.name_en,
+                        }));
+                    }
+                    response.fields.push(serde_json::json!({
+                        "module_type": "Review",
+                        "key": format!("{}_score", review_key_prefix),
+                        "value_type": "number"
+                    }));
+                    response.fields.push(serde_json::json!({
+                        "module_type": "Review",
+                        "key": format!("{}_rank", review_key_prefix),
+                        "value_type": "number"
+                    }));
+                    let reviews = review_schema.get_ticket_reviews(&mut conn).await.map_err(|err| AppError::internal(err.to_string()))?;
+                    rankings.insert(schema_flow.schema.id, rubric.rank(reviews));
+                }
             }
         }
     }
@@ -840,20 +2422,75 @@ async fn export_tickets_for_schema_in_admin<'a>(
         let mut ticket_data = Map::new();
         ticket_data.insert("ticket_id".to_owned(), serde_json::Value::Number(serde_json::Number::from(ticket.id)));
         for flow in ticket.get_flows(&mut conn).await.map_err(|err| AppError::internal(err.to_string()))? {
+            if flow.flow.skipped {
+                continue;
+            }
             let schema_flow = schema_flows.iter().find(|f| f.schema.id == flow.flow.ticket_schema_flow_id).expect("Schema flow not found");
 
             match flow.module {
                 TicketFlowValue::Form(form) => {
                     if let serde_json::Value::Object(form_value) = form.value {
+                        for (form_id, key) in &number_keys {
+                            if *form_id != form.ticket_schema_form_id {
+                                continue;
+                            }
+                            if let Some(value) = form_value.get(key).and_then(|value| value.as_f64()) {
+                                let total_key = format!("{}.{}", form_id, key);
+                                let total = response.totals.get(&total_key).and_then(|total| total.as_f64()).unwrap_or(0.0);
+                                response.totals.insert(total_key, serde_json::json!(total + value));
+                            }
+                        }
                         for (key, value) in form_value {
-                            ticket_data.insert(key, value);
+                            if let Some(field) = choice_fields.iter().find(|field| field.key == key)
+                            {
+                                if let Some(label) = field.define.option_label(&value, i18n.locale)
+                                {
+                                    ticket_data
+                                        .insert(format!("{}_label", key), Value::String(label));
+                                }
+                                if let Some(text) = field.define.other_text(&value) {
+                                    ticket_data
+                                        .insert(format!("{}_other", key), Value::String(text));
+                                }
+                            }
+                            if !groups.iter().any(|(_, field)| field.key == key) {
+                                ticket_data.insert(key, value);
+                                continue;
+                            }
+                            let items = match value {
+                                serde_json::Value::Array(items) => items,
+                                _ => continue,
+                            };
+                            let size = group_sizes.entry(key.clone()).or_insert(0);
+                            *size = (*size).max(items.len());
+                            for (index, item) in items.into_iter().enumerate() {
+                                if let serde_json::Value::Object(item) = item {
+                                    for (item_key, item_value) in item {
+                                        ticket_data.insert(
+                                            format!("{}.{}.{}", key, index + 1, item_key),
+                                            item_value,
+                                        );
+                                    }
+                                }
+                            }
                         }
                     }
                 },
                 TicketFlowValue::Review(review) => {
                     let review_key_prefix = format!("review_{}", schema_flow.schema.order);
-                    ticket_data.insert(format!("{}_approved", review_key_prefix), serde_json::Value::Bool(review.approved));
-                    ticket_data.insert(format!("{}_comment", review_key_prefix), serde_json::Value::String(review.comment.unwrap_or("".to_owned())));
+                    let approved = match &review.tally {
+                        Some(tally) => tally.decision.map_or(serde_json::Value::Null, serde_json::Value::Bool),
+                        None => serde_json::Value::Bool(review.review.approved),
+                    };
+                    ticket_data.insert(format!("{}_approved", review_key_prefix), approved);
+                    ticket_data.insert(format!("{}_comment", review_key_prefix), serde_json::Value::String(review.review.comment.unwrap_or("".to_owned())));
+                    if let Some(score) = rankings.get(&schema_flow.schema.id).and_then(|scores| scores.iter().find(|score| score.ticket_id == ticket.id)) {
+                        for (key, value) in &score.criteria {
+                            ticket_data.insert(format!("{}_{}", review_key_prefix, key), serde_json::json!(value));
+                        }
+                        ticket_data.insert(format!("{}_score", review_key_prefix), serde_json::json!(score.score));
+                        ticket_data.insert(format!("{}_rank", review_key_prefix), serde_json::json!(score.rank));
+                    }
                 },
                 TicketFlowValue::None => ()
             }
@@ -861,18 +2498,400 @@ async fn export_tickets_for_schema_in_admin<'a>(
         response.data.push(ticket_data);
     }
 
+    for (position, form_field) in groups.into_iter().rev() {
+        let fields = match &form_field.define {
+            FormFieldDefine::Group { fields, .. } => fields,
+            _ => continue,
+        };
+        let size = group_sizes.get(&form_field.key).cloned().unwrap_or(0);
+        let mut columns = vec![];
+        for index in 1..=size {
+            for field in fields {
+                columns.push(serde_json::json!({
+                    "module_type": "Form",
+                    "key": format!("{}.{}.{}", form_field.key, index, field.key),
+                    "value_type": field.define.value_type(),
+                    "define": field.define,
+                    "name_zh": format!("{} {} {}", form_field.name_zh, index, field.name_zh),
+                    "name_en": format!("{} {} {}", form_field.name_en, index, field.name_en),
+                }));
+            }
+        }
+        response.fields.splice(position..position, columns);
+    }
+
+    for (form_id, key) in number_keys {
+        response.totals.entry(format!("{}.{}", form_id, key)).or_insert(serde_json::json!(0.0));
+    }
 
     Ok(Json(response))
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketReviewRanking {
+    pub schema: TicketSchemaFlow,
+    pub rubric: ReviewRubric,
+    pub scores: Vec<TicketReviewRankingItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketReviewRankingItem {
+    pub title: String,
+    #[serde(flatten)]
+    pub score: TicketReviewScore,
+}
+
+#[get("/ticket/admin/schemas/<schema_id>/rankings")]
+async fn rankings_for_schema_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+) -> JsonResult<Vec<TicketReviewRanking>> {
+    let AuthGuard { user, .. } = auth;
+    let schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let tickets = schema
+        .get_tickets(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let schema_flows = schema
+        .get_detail_flows(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let mut result = vec![];
+    for schema_flow in schema_flows {
+        if let TicketSchemaFlowValue::Review(review_schema) = schema_flow.module {
+            let rubric = match review_schema.rubric.clone() {
+                Some(rubric) => rubric,
+                None => continue,
+            };
+            let reviews = review_schema
+                .get_ticket_reviews(&mut conn)
+                .await
+                .map_err(|err| AppError::internal(err.to_string()))?;
+            let scores = rubric
+                .rank(reviews)
+                .into_iter()
+                .filter_map(|score| {
+                    let ticket = tickets.iter().find(|ticket| ticket.id == score.ticket_id)?;
+                    Some(TicketReviewRankingItem {
+                        title: ticket.title.clone(),
+                        score,
+                    })
+                })
+                .collect();
+            result.push(TicketReviewRanking {
+                schema: schema_flow.schema,
+                rubric,
+                scores,
+            });
+        }
+    }
+
+    Ok(Json(result))
+}
+
+#[get("/ticket/admin/schemas/<schema_id>/statistics")]
+async fn statistics_for_schema_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+) -> JsonResult<Vec<FormNumberSummary>> {
+    let AuthGuard { user, .. } = auth;
+    let schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let schema_flows = schema
+        .get_detail_flows(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let answers = TicketFormAnswer::get_schema_answers(&mut conn, schema.id)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let mut result = vec![];
+    for schema_flow in schema_flows {
+        let form = match schema_flow.module {
+            TicketSchemaFlowValue::Form(form) => form,
+            _ => continue,
+        };
+        for field in form.fields {
+            let unit = match &field.define {
+                FormFieldDefine::Number { unit, .. } => unit.clone(),
+                _ => continue,
+            };
+            let values = answers
+                .iter()
+                .filter(|answer| answer.ticket_schema_form_id == form.form.id)
+                .filter_map(|answer| {
+                    answer
+                        .value
+                        .get(&field.key)
+                        .and_then(|value| value.as_f64())
+                })
+                .collect();
+            result.push(FormNumberSummary::new(
+                form.form.id,
+                field.key,
+                unit,
+                values,
+            ));
+        }
+    }
+
+    Ok(Json(result))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketConflictReport {
+    #[serde(flatten)]
+    pub conflict: TicketConflict,
+    pub user: User,
+}
+
+#[get("/ticket/admin/schemas/<schema_id>/conflicts")]
+async fn conflicts_for_schema_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+) -> JsonResult<Vec<TicketConflictReport>> {
+    let AuthGuard { user, .. } = auth;
+    let schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let conflicts = TicketConflict::get_schema_conflicts(&mut conn, &schema)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?
+        .into_iter()
+        .map(|(conflict, user)| TicketConflictReport { conflict, user })
+        .collect();
+    Ok(Json(conflicts))
+}
+
+#[get("/ticket/admin/schemas/<schema_id>/spawns")]
+async fn spawns_for_schema_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+) -> JsonResult<Vec<TicketSchemaSpawn>> {
+    let AuthGuard { user, .. } = auth;
+    let schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let spawns = TicketSchemaSpawn::get_schema_spawns(&mut conn, schema.id)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    Ok(Json(spawns))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct AddTicketSpawnReq {
+    pub target_schema_id: i32,
+    pub mappings: TicketSpawnMappings,
+    pub operators: TicketSpawnOperators,
+}
+
+#[post("/ticket/admin/schemas/<schema_id>/spawns", data = "<new_spawn_req>")]
+async fn add_spawn_to_schema_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+    new_spawn_req: Json<AddTicketSpawnReq>,
+) -> JsonResult<TicketSchemaSpawn> {
+    let AuthGuard { user, .. } = auth;
+    let AddTicketSpawnReq {
+        target_schema_id,
+        mappings,
+        operators,
+    } = new_spawn_req.into_inner();
+
+    let schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    let target_schema = TicketSchema::find(&mut conn, target_schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    for schema in [&schema, &target_schema] {
+        match schema.is_manager(&mut conn, &user).await {
+            Ok(false) => {
+                return Err(AppError::forbidden(
+                    i18n.t("ticket.error.not_manager_of_this_schema"),
+                ))
+            }
+            Err(err) => return Err(AppError::forbidden(err.to_string())),
+            _ => (),
+        }
+    }
+
+    if target_schema.project_id != schema.project_id || target_schema.id == schema.id {
+        return Err(AppError::bad_request(
+            i18n.t("ticket.error.spawn_target_not_in_project"),
+        ));
+    }
+
+    let schema_flows = schema
+        .get_flows(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    let target_flows = target_schema
+        .get_detail_flows(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+
+    let is_valid_mappings = mappings.0.iter().all(|mapping| {
+        target_flows.iter().any(|flow| match &flow.module {
+            TicketSchemaFlowValue::Form(form) => {
+                form.form.id == mapping.schema_form_id
+                    && form
+                        .fields
+                        .iter()
+                        .any(|field| field.key == mapping.field_key)
+            }
+            TicketSchemaFlowValue::Review(_) => false,
+        })
+    });
+    let is_valid_operators = operators.0.iter().all(|operator| {
+        let is_valid_from = match &operator.from {
+            TicketSpawnOperatorFrom::User { .. } => true,
+            TicketSpawnOperatorFrom::Flow { schema_flow_id } => {
+                schema_flows.iter().any(|flow| flow.id == *schema_flow_id)
+            }
+        };
+        is_valid_from
+            && target_flows
+                .iter()
+                .any(|flow| flow.schema.id == operator.schema_flow_id)
+    });
+    if !is_valid_mappings || !is_valid_operators {
+        return Err(AppError::bad_request("Invalid spawn".to_owned()));
+    }
+
+    for operator in operators.0.iter() {
+        if let TicketSpawnOperatorFrom::User { user_id } = &operator.from {
+            let is_project_user = match User::find(&mut conn, user_id.clone()).await {
+                Ok(operator_user) => operator_user.project_id == target_schema.project_id,
+                Err(diesel::result::Error::NotFound) => false,
+                Err(err) => return Err(AppError::internal(err.to_string())),
+            };
+            if !is_project_user {
+                return Err(AppError::bad_request(
+                    i18n.t("ticket.error.spawn_operator_not_in_project"),
+                ));
+            }
+        }
+    }
+
+    let is_cycle = TicketSchemaSpawn::is_reachable(&mut conn, target_schema.id, schema.id)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    if is_cycle {
+        return Err(AppError::bad_request(i18n.t("ticket.error.spawn_cycle")));
+    }
+
+    let spawn = TicketSchemaSpawn::create(&mut conn, &schema, &target_schema, mappings, operators)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    Ok(Json(spawn))
+}
+
+#[delete("/ticket/admin/schemas/<schema_id>/spawns/<spawn_id>")]
+async fn delete_spawn_in_admin<'a>(
+    mut conn: DbConn,
+    auth: AuthGuard,
+    i18n: I18n<'a>,
+    schema_id: i32,
+    spawn_id: i32,
+) -> EmptyResult {
+    let AuthGuard { user, .. } = auth;
+    let schema = TicketSchema::find(&mut conn, schema_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    match schema.is_manager(&mut conn, &user).await {
+        Ok(false) => {
+            return Err(AppError::forbidden(
+                i18n.t("ticket.error.not_manager_of_this_schema"),
+            ))
+        }
+        Err(err) => return Err(AppError::forbidden(err.to_string())),
+        _ => (),
+    }
+
+    let spawn = TicketSchemaSpawn::find(&mut conn, spawn_id)
+        .await
+        .map_err(|err| AppError::not_found(err.to_string()))?;
+    if spawn.ticket_schema_id != schema.id {
+        return Err(AppError::not_found("Spawn not found".to_owned()));
+    }
+
+    spawn
+        .delete(&mut conn)
+        .await
+        .map_err(|err| AppError::internal(err.to_string()))?;
+    Ok(EmptyResponse)
+}
+
 pub fn routes() -> Vec<Route> {
     routes![
         all_tickets,
         get_ticket,
         process_ticket_flow,
+        save_ticket_flow_draft,
+        claim_ticket_flow,
+        release_ticket_flow,
         all_probably_schemas,
         get_schema,
         get_probably_assign_user_in_schema_flow,
+        declare_conflict_in_ticket,
+        declare_conflict_in_schema,
+        all_conflicts,
+        delete_conflict,
         add_ticket_for_schema,
         upload_file_in_form_field,
         get_field_file_content,
@@ -880,8 +2899,22 @@ pub fn routes() -> Vec<Route> {
         all_managed_schemas_in_admin,
         get_managed_schema_in_admin,
         add_managed_schema_in_admin,
+        update_schema_dates_in_admin,
         add_flow_to_schema_in_admin,
+        rebalance_flow_in_admin,
+        reassign_ticket_flow_in_admin,
+        edit_ticket_flow_answer_in_admin,
+        complete_ticket_flow_in_admin,
+        skip_ticket_flow_in_admin,
+        add_bulk_tickets_for_schema_in_admin,
+        add_bulk_tickets_from_csv_for_schema_in_admin,
         all_tickets_for_schema_in_admin,
         export_tickets_for_schema_in_admin,
+        rankings_for_schema_in_admin,
+        statistics_for_schema_in_admin,
+        conflicts_for_schema_in_admin,
+        spawns_for_schema_in_admin,
+        add_spawn_to_schema_in_admin,
+        delete_spawn_in_admin,
     ]
 }
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use diesel::{
+    deserialize::FromSql,
+    mysql::{Mysql, MysqlValue},
+    serialize::ToSql,
+    sql_types,
+};
+use rand::seq::SliceRandom;
+use rocket::serde::json::serde_json;
+
+use crate::models::user::User;
+
+pub mod models;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum FlowAssignmentStrategy {
+    RoundRobin,
+    LeastLoaded,
+    Random,
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::Json)]
+pub struct FlowAssignmentDefine {
+    pub strategy: FlowAssignmentStrategy,
+    pub count: u32,
+}
+
+impl FromSql<sql_types::Json, Mysql> for FlowAssignmentDefine {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<FlowAssignmentDefine>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for FlowAssignmentDefine {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketFlowAssignment {
+    pub ticket_id: i32,
+    pub ticket_flow_id: i32,
+    pub users: Vec<User>,
+}
+
+impl FlowAssignmentDefine {
+    pub fn pick(
+        &self,
+        mut candidates: Vec<User>,
+        assigned_count: i64,
+        loads: &HashMap<String, i64>,
+    ) -> Vec<User> {
+        if candidates.is_empty() {
+            return vec![];
+        }
+
+        let count = (self.count.max(1) as usize).min(candidates.len());
+        candidates.sort_by(|a, b| a.id.cmp(&b.id));
+
+        match self.strategy {
+            FlowAssignmentStrategy::RoundRobin => {
+                let start = assigned_count as usize % candidates.len();
+                candidates.rotate_left(start);
+            }
+            FlowAssignmentStrategy::LeastLoaded => {
+                candidates.sort_by_key(|user| loads.get(&user.id).cloned().unwrap_or(0));
+            }
+            FlowAssignmentStrategy::Random => {
+                candidates.shuffle(&mut rand::thread_rng());
+            }
+        }
+
+        candidates.truncate(count);
+        candidates
+    }
+}
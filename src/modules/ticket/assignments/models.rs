@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use rocket_db_pools::diesel::prelude::RunQueryDsl;
+
+use crate::models::user::User;
+use crate::modules::ticket::models::{TicketFlow, TicketSchemaFlow};
+use crate::schema::{ticket_flow_assignees, ticket_flows, users};
+use crate::utils::serde::unix_time;
+
+#[derive(
+    Queryable,
+    Identifiable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Insertable,
+    AsChangeset,
+)]
+#[diesel(belongs_to(TicketFlow))]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = ticket_flow_assignees)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct TicketFlowAssignee {
+    pub id: i32,
+    pub ticket_flow_id: i32,
+    pub user_id: String,
+    #[serde(with = "unix_time")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "unix_time")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl TicketFlowAssignee {
+    pub async fn create(
+        conn: &mut crate::DbConn,
+        ticket_flow: &TicketFlow,
+        users: &[User],
+    ) -> Result<usize, diesel::result::Error> {
+        let records = users
+            .iter()
+            .map(|user| {
+                (
+                    ticket_flow_assignees::ticket_flow_id.eq(ticket_flow.id),
+                    ticket_flow_assignees::user_id.eq(user.id.clone()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        diesel::insert_into(ticket_flow_assignees::table)
+            .values(records)
+            .execute(conn)
+            .await
+    }
+
+    pub async fn get_flow_assignees(
+        conn: &mut crate::DbConn,
+        ticket_flow: &TicketFlow,
+    ) -> Result<Vec<User>, diesel::result::Error> {
+        TicketFlowAssignee::belonging_to(ticket_flow)
+            .inner_join(users::table)
+            .order(users::id.asc())
+            .select(User::as_select())
+            .load(conn)
+            .await
+    }
+
+    pub async fn delete_flow_assignees(
+        conn: &mut crate::DbConn,
+        ticket_flow: &TicketFlow,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(
+            ticket_flow_assignees::table
+                .filter(ticket_flow_assignees::ticket_flow_id.eq(ticket_flow.id)),
+        )
+        .execute(conn)
+        .await
+    }
+
+    pub async fn delete_flow_assignee(
+        conn: &mut crate::DbConn,
+        ticket_flow: &TicketFlow,
+        user_id: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(
+            ticket_flow_assignees::table
+                .filter(ticket_flow_assignees::ticket_flow_id.eq(ticket_flow.id))
+                .filter(ticket_flow_assignees::user_id.eq(user_id)),
+        )
+        .execute(conn)
+        .await
+    }
+
+    pub async fn count_schema_flow_assignments(
+        conn: &mut crate::DbConn,
+        schema_flow: &TicketSchemaFlow,
+    ) -> Result<i64, diesel::result::Error> {
+        ticket_flow_assignees::table
+            .inner_join(ticket_flows::table)
+            .filter(ticket_flows::ticket_schema_flow_id.eq(schema_flow.id))
+            .count()
+            .get_result(conn)
+            .await
+    }
+
+    pub async fn get_schema_flow_loads(
+        conn: &mut crate::DbConn,
+        schema_flow: &TicketSchemaFlow,
+    ) -> Result<HashMap<String, i64>, diesel::result::Error> {
+        let loads: Vec<(String, i64)> = ticket_flow_assignees::table
+            .inner_join(ticket_flows::table)
+            .filter(ticket_flows::ticket_schema_flow_id.eq(schema_flow.id))
+            .filter(ticket_flows::finished.eq(false))
+            .group_by(ticket_flow_assignees::user_id)
+            .select((ticket_flow_assignees::user_id, count_star()))
+            .load(conn)
+            .await?;
+
+        Ok(loads.into_iter().collect())
+    }
+}
@@ -0,0 +1,120 @@
+use diesel::{
+    deserialize::FromSql,
+    mysql::{Mysql, MysqlValue},
+    serialize::ToSql,
+    sql_types,
+};
+use rocket::serde::json::serde_json;
+
+use super::forms::conditions::FormFieldCondition;
+use super::forms::fields::{FormFieldDefault, FormFieldValue};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FlowConditionValue {
+    Condition(FormFieldCondition),
+    Equal {
+        from: FormFieldDefault,
+        value: Vec<FormFieldValue>,
+    },
+}
+
+impl From<FlowConditionValue> for FlowCondition {
+    fn from(value: FlowConditionValue) -> Self {
+        match value {
+            FlowConditionValue::Condition(condition) => FlowCondition(condition),
+            FlowConditionValue::Equal { from, value } => {
+                FlowCondition(FormFieldCondition::Equal { from, value })
+            }
+        }
+    }
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::Json)]
+#[serde(from = "FlowConditionValue")]
+pub struct FlowCondition(pub FormFieldCondition);
+
+impl FromSql<sql_types::Json, Mysql> for FlowCondition {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<FlowCondition>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for FlowCondition {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+impl FlowCondition {
+    pub async fn is_satisfied(&self, conn: &mut crate::DbConn, ticket_id: &i32) -> bool {
+        self.0.resolve(conn, ticket_id).await.is_met()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::serde::json::serde_json::{self, json};
+
+    use super::FlowCondition;
+    use crate::modules::ticket::forms::conditions::FormFieldCondition;
+    use crate::modules::ticket::forms::fields::{FormFieldDefault, FormFieldValue};
+
+    fn from_static(value: &str) -> FormFieldDefault {
+        FormFieldDefault::Static(FormFieldValue::String(value.to_owned()))
+    }
+
+    #[test]
+    fn deserialize_legacy_equal() {
+        let condition: FlowCondition = serde_json::from_value(json!({
+            "from": { "type": "Static", "content": "yes" },
+            "value": ["yes", "maybe"]
+        }))
+        .unwrap();
+        assert_eq!(
+            condition,
+            FlowCondition(FormFieldCondition::Equal {
+                from: from_static("yes"),
+                value: vec![
+                    FormFieldValue::String("yes".to_owned()),
+                    FormFieldValue::String("maybe".to_owned()),
+                ],
+            })
+        );
+        assert!(condition.0.is_met());
+    }
+
+    #[test]
+    fn deserialize_tagged_condition() {
+        let condition: FlowCondition = serde_json::from_value(json!({
+            "type": "NotEmpty",
+            "from": { "type": "Static", "content": "" }
+        }))
+        .unwrap();
+        assert_eq!(
+            condition,
+            FlowCondition(FormFieldCondition::NotEmpty {
+                from: from_static(""),
+            })
+        );
+        assert!(!condition.0.is_met());
+    }
+
+    #[test]
+    fn serialize_as_tagged_condition() {
+        let condition: FlowCondition = serde_json::from_value(json!({
+            "from": { "type": "Static", "content": "yes" },
+            "value": ["yes"]
+        }))
+        .unwrap();
+        let value = serde_json::to_value(&condition).unwrap();
+        assert_eq!(value["type"], json!("Equal"));
+        assert_eq!(
+            serde_json::from_value::<FlowCondition>(value).unwrap(),
+            condition
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rocket::serde::json::serde_json;
+use rocket_db_pools::diesel::prelude::RunQueryDsl;
+use serde_json::Value;
+
+use crate::models::user::User;
+use crate::modules::ticket::models::{Ticket, TicketSchema};
+use crate::schema::{ticket_conflicts, ticket_flows, ticket_form_answers, tickets, users};
+use crate::utils::serde::unix_time;
+
+#[derive(
+    Queryable,
+    Identifiable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Insertable,
+    AsChangeset,
+)]
+#[diesel(belongs_to(TicketSchema))]
+#[diesel(belongs_to(Ticket))]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = ticket_conflicts)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct TicketConflict {
+    pub id: i32,
+    pub ticket_schema_id: i32,
+    pub ticket_id: Option<i32>,
+    pub user_id: String,
+    pub field_key: Option<String>,
+    pub field_value: Option<String>,
+    pub reason: Option<String>,
+    #[serde(with = "unix_time")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "unix_time")]
+    pub updated_at: NaiveDateTime,
+}
+
+fn is_field_value_match(value: &Value, expected: &str) -> bool {
+    match value {
+        Value::String(value) => value.trim().eq_ignore_ascii_case(expected.trim()),
+        Value::Number(value) => value.to_string() == expected.trim(),
+        Value::Array(values) => values
+            .iter()
+            .any(|value| is_field_value_match(value, expected)),
+        _ => false,
+    }
+}
+
+impl TicketConflict {
+    pub async fn create(
+        conn: &mut crate::DbConn,
+        schema: &TicketSchema,
+        ticket_id: Option<i32>,
+        user: &User,
+        field: Option<(String, String)>,
+        reason: Option<String>,
+    ) -> Result<TicketConflict, diesel::result::Error> {
+        let (field_key, field_value) = field.unzip();
+        diesel::insert_into(ticket_conflicts::table)
+            .values((
+                ticket_conflicts::ticket_schema_id.eq(schema.id),
+                ticket_conflicts::ticket_id.eq(ticket_id),
+                ticket_conflicts::user_id.eq(user.id.clone()),
+                ticket_conflicts::field_key.eq(field_key),
+                ticket_conflicts::field_value.eq(field_value),
+                ticket_conflicts::reason.eq(reason),
+            ))
+            .execute(conn)
+            .await?;
+
+        sql_function! {
+            fn last_insert_id() -> Integer;
+        }
+
+        ticket_conflicts::table
+            .find(last_insert_id())
+            .first(conn)
+            .await
+    }
+
+    pub async fn find(
+        conn: &mut crate::DbConn,
+        id: i32,
+    ) -> Result<TicketConflict, diesel::result::Error> {
+        ticket_conflicts::table.find(id).first(conn).await
+    }
+
+    pub async fn delete(&self, conn: &mut crate::DbConn) -> Result<usize, diesel::result::Error> {
+        diesel::delete(ticket_conflicts::table.find(self.id))
+            .execute(conn)
+            .await
+    }
+
+    pub async fn get_user_conflicts(
+        conn: &mut crate::DbConn,
+        user: &User,
+    ) -> Result<Vec<TicketConflict>, diesel::result::Error> {
+        TicketConflict::belonging_to(user)
+            .order(ticket_conflicts::created_at.desc())
+            .select(TicketConflict::as_select())
+            .load(conn)
+            .await
+    }
+
+    pub async fn get_schema_conflicts(
+        conn: &mut crate::DbConn,
+        schema: &TicketSchema,
+    ) -> Result<Vec<(TicketConflict, User)>, diesel::result::Error> {
+        TicketConflict::belonging_to(schema)
+            .inner_join(users::table)
+            .order(ticket_conflicts::created_at.desc())
+            .select((TicketConflict::as_select(), User::as_select()))
+            .load(conn)
+            .await
+    }
+
+    pub async fn get_conflicted_user_ids(
+        conn: &mut crate::DbConn,
+        ticket: &Ticket,
+    ) -> Result<Vec<String>, diesel::result::Error> {
+        let conflicts: Vec<TicketConflict> = ticket_conflicts::table
+            .filter(ticket_conflicts::ticket_schema_id.eq(ticket.ticket_schema_id))
+            .filter(
+                ticket_conflicts::ticket_id
+                    .eq(ticket.id)
+                    .or(ticket_conflicts::ticket_id.is_null()),
+            )
+            .select(TicketConflict::as_select())
+            .load(conn)
+            .await?;
+
+        let answers: Vec<Value> = ticket_form_answers::table
+            .inner_join(ticket_flows::table)
+            .filter(ticket_flows::ticket_id.eq(ticket.id))
+            .select(ticket_form_answers::value)
+            .load(conn)
+            .await?;
+
+        Ok(conflicts
+            .into_iter()
+            .filter(|conflict| conflict.is_match(ticket.id, &answers))
+            .map(|conflict| conflict.user_id)
+            .collect())
+    }
+
+    pub async fn get_conflicted_ticket_ids(
+        conn: &mut crate::DbConn,
+        user: &User,
+        candidate_ticket_ids: &Vec<i32>,
+    ) -> Result<Vec<i32>, diesel::result::Error> {
+        if candidate_ticket_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let conflicts = TicketConflict::get_user_conflicts(conn, user).await?;
+
+        let mut ticket_ids = conflicts
+            .iter()
+            .filter_map(|conflict| conflict.ticket_id)
+            .filter(|ticket_id| candidate_ticket_ids.contains(ticket_id))
+            .collect::<Vec<_>>();
+
+        let field_schema_ids = conflicts
+            .iter()
+            .filter(|conflict| conflict.ticket_id.is_none())
+            .map(|conflict| conflict.ticket_schema_id)
+            .collect::<Vec<_>>();
+
+        if field_schema_ids.is_empty() {
+            return Ok(ticket_ids);
+        }
+
+        let answers: Vec<(i32, i32, Value)> = ticket_form_answers::table
+            .inner_join(ticket_flows::table.inner_join(tickets::table))
+            .filter(tickets::id.eq_any(candidate_ticket_ids))
+            .filter(tickets::ticket_schema_id.eq_any(&field_schema_ids))
+            .select((
+                tickets::id,
+                tickets::ticket_schema_id,
+                ticket_form_answers::value,
+            ))
+            .load(conn)
+            .await?;
+
+        for (ticket_id, schema_id, value) in answers.into_iter() {
+            let is_conflicted = conflicts.iter().any(|conflict| {
+                conflict.ticket_id.is_none()
+                    && conflict.ticket_schema_id == schema_id
+                    && conflict.is_match(ticket_id, std::slice::from_ref(&value))
+            });
+            if is_conflicted && !ticket_ids.contains(&ticket_id) {
+                ticket_ids.push(ticket_id);
+            }
+        }
+
+        Ok(ticket_ids)
+    }
+
+    pub async fn is_conflicted(
+        conn: &mut crate::DbConn,
+        ticket_id: i32,
+        user_id: &String,
+    ) -> Result<bool, diesel::result::Error> {
+        let ticket = Ticket::find(conn, ticket_id).await?;
+        let user_ids = TicketConflict::get_conflicted_user_ids(conn, &ticket).await?;
+        Ok(user_ids.contains(user_id))
+    }
+
+    pub fn is_match(&self, ticket_id: i32, answers: &[Value]) -> bool {
+        if let Some(conflict_ticket_id) = self.ticket_id {
+            return conflict_ticket_id == ticket_id;
+        }
+
+        match (&self.field_key, &self.field_value) {
+            (Some(field_key), Some(field_value)) => answers.iter().any(|answer| {
+                answer
+                    .get(field_key)
+                    .is_some_and(|value| is_field_value_match(value, field_value))
+            }),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use lettre::{message::header::ContentType, Message};
+use rocket::fairing::AdHoc;
+use rocket::serde::json::serde_json;
+use rocket_db_pools::Database;
+
+use crate::models::project::Project;
+use crate::models::user::User;
+use crate::utils::db::Connection;
+use crate::utils::i18n::I18n;
+use crate::utils::lettre::send_email;
+use crate::{AppConfig, DbConn, MainDb};
+
+use super::histories::models::TicketHistory;
+use super::histories::TicketHistoryAction;
+use super::models::{Ticket, TicketFlow, TicketSchema, TicketSchemaFlow};
+
+const ESCALATION_INTERVAL_SECS: u64 = 600;
+
+async fn send_escalation_email(
+    conn: &mut DbConn,
+    config: &AppConfig,
+    manager: &User,
+    schema: &TicketSchema,
+    schema_flow: &TicketSchemaFlow,
+    ticket: &Ticket,
+    flow: &TicketFlow,
+) -> Result<usize, String> {
+    let emails = manager
+        .get_emails(conn)
+        .await
+        .map_err(|err| err.to_string())?;
+    let project = Project::find(conn, schema.project_id.clone())
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let i18n = I18n::new(&manager.locale);
+    let (project_name, schema_title, flow_name) = if i18n.locale == "zh" {
+        (&project.name_zh, &schema.title_zh, &schema_flow.name_zh)
+    } else {
+        (&project.name_en, &schema.title_en, &schema_flow.name_en)
+    };
+    let email_from = config.email_from.clone();
+    let due_at = flow
+        .due_at
+        .map(|due_at| due_at.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default();
+
+    let subject = i18n.tf(
+        "ticket.email.overdue.subject",
+        &[
+            ("project", project_name.clone()),
+            ("ticket", ticket.title.clone()),
+        ],
+    );
+    let body = i18n.tf(
+        "ticket.email.overdue.body",
+        &[
+            ("user", manager.name.clone()),
+            ("schema", schema_title.clone()),
+            ("ticket", ticket.title.clone()),
+            ("flow", flow_name.clone()),
+            ("due_at", due_at),
+            ("email_from", email_from.clone()),
+        ],
+    );
+
+    let mut sent = 0;
+    for email in emails {
+        let message = Message::builder()
+            .from(
+                format!("ConfOps <{email_from}>")
+                    .parse()
+                    .map_err(|err: lettre::address::AddressError| err.to_string())?,
+            )
+            .to(format!("{} <{email}>", manager.name)
+                .parse()
+                .map_err(|err: lettre::address::AddressError| err.to_string())?)
+            .subject(subject.clone())
+            .header(ContentType::TEXT_HTML)
+            .body(body.clone())
+            .map_err(|err| err.to_string())?;
+
+        match send_email(config, message).await {
+            Ok(_) => sent += 1,
+            Err(err) => error!("Failed to send escalation email to {email}: {err}"),
+        }
+    }
+
+    Ok(sent)
+}
+
+async fn escalate_flow(
+    conn: &mut DbConn,
+    config: &AppConfig,
+    mut flow: TicketFlow,
+) -> Result<(), diesel::result::Error> {
+    let ticket = Ticket::find(conn, flow.ticket_id).await?;
+    let schema = TicketSchema::find(conn, ticket.ticket_schema_id).await?;
+    let schema_flow = TicketSchemaFlow::find(conn, flow.ticket_schema_flow_id).await?;
+    let managers = schema.get_managers(conn).await?;
+
+    let mut notified = vec![];
+    for manager in managers.iter() {
+        match send_escalation_email(conn, config, manager, &schema, &schema_flow, &ticket, &flow)
+            .await
+        {
+            Ok(0) => (),
+            Ok(_) => notified.push(manager.id.clone()),
+            Err(err) => error!("Failed to send escalation email to {}: {err}", manager.id),
+        }
+    }
+    if notified.is_empty() {
+        warn!("No manager notified for overdue flow {}", flow.id);
+        return Ok(());
+    }
+
+    let _ = flow.set_escalated(conn).await?;
+    let _ = TicketHistory::create(
+        conn,
+        ticket.id,
+        Some(&flow),
+        None,
+        TicketHistoryAction::Escalate,
+        Some(serde_json::json!({
+            "due_at": flow.due_at.map(|due_at| due_at.timestamp()),
+            "managers": notified,
+        })),
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn escalate_overdue_flows(
+    conn: &mut DbConn,
+    config: &AppConfig,
+) -> Result<(), diesel::result::Error> {
+    let flows = TicketFlow::get_overdue_flows(conn).await?;
+
+    for flow in flows.into_iter() {
+        let flow_id = flow.id;
+        if let Err(err) = escalate_flow(conn, config, flow).await {
+            error!("Failed to escalate overdue flow {flow_id}: {err}");
+        }
+    }
+
+    Ok(())
+}
+
+pub fn stage() -> AdHoc {
+    AdHoc::on_liftoff("Ticket Escalation", |rocket| {
+        Box::pin(async move {
+            let db = MainDb::fetch(rocket)
+                .expect("Failed to fetch database")
+                .clone();
+            let config = rocket
+                .state::<AppConfig>()
+                .expect("Failed to fetch config")
+                .clone();
+
+            tokio::spawn(async move {
+                let mut interval =
+                    tokio::time::interval(std::time::Duration::from_secs(ESCALATION_INTERVAL_SECS));
+                loop {
+                    interval.tick().await;
+                    let mut conn = match Connection::get(&db).await {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            error!("Failed to get connection for escalation: {err:?}");
+                            continue;
+                        }
+                    };
+                    if let Err(err) = escalate_overdue_flows(&mut conn, &config).await {
+                        error!("Failed to escalate overdue flows: {err}");
+                    }
+                }
+            });
+        })
+    })
+}
@@ -0,0 +1,273 @@
+use std::cmp::Ordering;
+
+use super::fields::{FormFieldDefault, FormFieldOptionValue, FormFieldValue};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum FormCompareOperator {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(tag = "type")]
+pub enum FormFieldCondition {
+    Equal {
+        from: FormFieldDefault,
+        value: Vec<FormFieldValue>,
+    },
+    NotEqual {
+        from: FormFieldDefault,
+        value: Vec<FormFieldValue>,
+    },
+    Includes {
+        from: FormFieldDefault,
+        value: Vec<FormFieldOptionValue>,
+    },
+    Compare {
+        from: FormFieldDefault,
+        operator: FormCompareOperator,
+        value: f64,
+    },
+    Empty {
+        from: FormFieldDefault,
+    },
+    NotEmpty {
+        from: FormFieldDefault,
+    },
+    And {
+        conditions: Vec<FormFieldCondition>,
+    },
+    Or {
+        conditions: Vec<FormFieldCondition>,
+    },
+}
+
+impl FormCompareOperator {
+    pub fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            FormCompareOperator::Gt => ordering == Ordering::Greater,
+            FormCompareOperator::Gte => ordering != Ordering::Less,
+            FormCompareOperator::Lt => ordering == Ordering::Less,
+            FormCompareOperator::Lte => ordering != Ordering::Greater,
+        }
+    }
+}
+
+impl FormFieldCondition {
+    pub async fn resolve(&self, conn: &mut crate::DbConn, ticket_id: &i32) -> FormFieldCondition {
+        let mut condition = self.clone();
+        match &mut condition {
+            FormFieldCondition::Equal { from, .. }
+            | FormFieldCondition::NotEqual { from, .. }
+            | FormFieldCondition::Includes { from, .. }
+            | FormFieldCondition::Compare { from, .. }
+            | FormFieldCondition::Empty { from }
+            | FormFieldCondition::NotEmpty { from } => {
+                *from = from.resolve(conn, ticket_id).await;
+            }
+            FormFieldCondition::And { conditions } | FormFieldCondition::Or { conditions } => {
+                for condition in conditions.iter_mut() {
+                    *condition = Box::pin(condition.resolve(conn, ticket_id)).await;
+                }
+            }
+        }
+        condition
+    }
+
+    pub fn is_met(&self) -> bool {
+        match self {
+            FormFieldCondition::Equal { from, value } => from.is_in(value),
+            FormFieldCondition::NotEqual { from, value } => !from.is_in(value),
+            FormFieldCondition::Includes { from, value } => match from.value() {
+                Some(FormFieldValue::Array(selected)) => {
+                    selected.iter().any(|option| value.contains(option))
+                }
+                Some(FormFieldValue::String(selected)) => {
+                    value.contains(&FormFieldOptionValue::String(selected.clone()))
+                }
+                Some(FormFieldValue::Integer(selected)) => {
+                    value.contains(&FormFieldOptionValue::Integer(*selected as i64))
+                }
+                _ => false,
+            },
+            FormFieldCondition::Compare {
+                from,
+                operator,
+                value,
+            } => from
+                .value()
+                .and_then(|from| from.as_f64())
+                .and_then(|from| from.partial_cmp(value))
+                .is_some_and(|ordering| operator.accepts(ordering)),
+            FormFieldCondition::Empty { from } => from.value().is_none_or(|from| from.is_blank()),
+            FormFieldCondition::NotEmpty { from } => {
+                from.value().is_some_and(|from| !from.is_blank())
+            }
+            FormFieldCondition::And { conditions } => conditions.iter().all(|c| c.is_met()),
+            FormFieldCondition::Or { conditions } => conditions.iter().any(|c| c.is_met()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::serde::json::serde_json::{self, json};
+
+    use super::{FormCompareOperator, FormFieldCondition};
+    use crate::modules::ticket::forms::fields::{
+        FormFieldDefault, FormFieldOptionValue, FormFieldValue,
+    };
+
+    fn from(value: FormFieldValue) -> FormFieldDefault {
+        FormFieldDefault::Static(value)
+    }
+
+    fn text(value: &str) -> FormFieldValue {
+        FormFieldValue::String(value.to_owned())
+    }
+
+    #[test]
+    fn equal_and_not_equal() {
+        let values = vec![text("a"), text("b")];
+        let condition = FormFieldCondition::Equal {
+            from: from(text("a")),
+            value: values.clone(),
+        };
+        assert!(condition.is_met());
+        let condition = FormFieldCondition::NotEqual {
+            from: from(text("a")),
+            value: values,
+        };
+        assert!(!condition.is_met());
+    }
+
+    #[test]
+    fn includes_selected_options() {
+        let value = vec![
+            FormFieldOptionValue::String("a".to_owned()),
+            FormFieldOptionValue::Integer(2),
+        ];
+        let includes = |selected: FormFieldValue| FormFieldCondition::Includes {
+            from: from(selected),
+            value: value.clone(),
+        };
+        assert!(includes(FormFieldValue::Array(vec![
+            FormFieldOptionValue::String("c".to_owned()),
+            FormFieldOptionValue::String("a".to_owned()),
+        ]))
+        .is_met());
+        assert!(includes(text("a")).is_met());
+        assert!(includes(FormFieldValue::Integer(2)).is_met());
+        assert!(!includes(text("c")).is_met());
+        assert!(!includes(FormFieldValue::Bool(true)).is_met());
+    }
+
+    #[test]
+    fn compare_numbers() {
+        let compare = |value: FormFieldValue, operator| FormFieldCondition::Compare {
+            from: from(value),
+            operator,
+            value: 10.0,
+        };
+        assert!(compare(FormFieldValue::Integer(11), FormCompareOperator::Gt).is_met());
+        assert!(!compare(FormFieldValue::Integer(10), FormCompareOperator::Gt).is_met());
+        assert!(compare(FormFieldValue::Float(10.0), FormCompareOperator::Gte).is_met());
+        assert!(compare(text(" 9.5 "), FormCompareOperator::Lt).is_met());
+        assert!(compare(FormFieldValue::Integer(10), FormCompareOperator::Lte).is_met());
+        assert!(!compare(text("ten"), FormCompareOperator::Lte).is_met());
+    }
+
+    #[test]
+    fn empty_and_not_empty() {
+        let unanswered = FormFieldDefault::Dynamic {
+            schema_form_id: 1,
+            flow_id: None,
+            field_key: "key".to_owned(),
+            value: None,
+        };
+        assert!(FormFieldCondition::Empty {
+            from: unanswered.clone()
+        }
+        .is_met());
+        assert!(!FormFieldCondition::NotEmpty { from: unanswered }.is_met());
+        assert!(FormFieldCondition::Empty {
+            from: from(text("  "))
+        }
+        .is_met());
+        assert!(FormFieldCondition::NotEmpty {
+            from: from(text("a"))
+        }
+        .is_met());
+    }
+
+    #[test]
+    fn nested_and_or() {
+        let met = FormFieldCondition::NotEmpty {
+            from: from(text("a")),
+        };
+        let unmet = FormFieldCondition::Empty {
+            from: from(text("a")),
+        };
+        let condition = FormFieldCondition::And {
+            conditions: vec![
+                met.clone(),
+                FormFieldCondition::Or {
+                    conditions: vec![unmet.clone(), met.clone()],
+                },
+            ],
+        };
+        assert!(condition.is_met());
+        let condition = FormFieldCondition::Or {
+            conditions: vec![
+                unmet.clone(),
+                FormFieldCondition::And {
+                    conditions: vec![met, unmet],
+                },
+            ],
+        };
+        assert!(!condition.is_met());
+        assert!(FormFieldCondition::And { conditions: vec![] }.is_met());
+        assert!(!FormFieldCondition::Or { conditions: vec![] }.is_met());
+    }
+
+    #[test]
+    fn deserialize_tagged_conditions() {
+        let condition: FormFieldCondition = serde_json::from_value(json!({
+            "type": "Or",
+            "conditions": [
+                {
+                    "type": "Compare",
+                    "from": { "type": "Static", "content": 3 },
+                    "operator": "gte",
+                    "value": 3.0
+                },
+                {
+                    "type": "Equal",
+                    "from": { "type": "Static", "content": "a" },
+                    "value": ["b"]
+                }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            condition,
+            FormFieldCondition::Or {
+                conditions: vec![
+                    FormFieldCondition::Compare {
+                        from: from(FormFieldValue::Integer(3)),
+                        operator: FormCompareOperator::Gte,
+                        value: 3.0,
+                    },
+                    FormFieldCondition::Equal {
+                        from: from(text("a")),
+                        value: vec![text("b")],
+                    },
+                ],
+            }
+        );
+        assert!(condition.is_met());
+    }
+}
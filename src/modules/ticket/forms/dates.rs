@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use diesel::prelude::*;
+use diesel::{
+    deserialize::FromSql,
+    mysql::{Mysql, MysqlValue},
+    serialize::ToSql,
+    sql_types,
+};
+use rocket::serde::json::serde_json;
+use rocket_db_pools::diesel::prelude::RunQueryDsl;
+
+use crate::schema::{ticket_schemas, tickets};
+
+use super::fields::{FormFieldDefault, FormFieldValue};
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+pub const TIME_FORMAT: &str = "%H:%M:%S";
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(tag = "type")]
+pub enum FormFieldDateBound {
+    Absolute {
+        value: String,
+    },
+    Relative {
+        from: FormFieldDefault,
+        #[serde(default)]
+        days: i64,
+        #[serde(default)]
+        minutes: i64,
+    },
+    Schema {
+        key: String,
+        #[serde(default)]
+        days: i64,
+        #[serde(default)]
+        minutes: i64,
+    },
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+#[diesel(sql_type = sql_types::Json)]
+pub struct TicketSchemaDates(pub HashMap<String, String>);
+
+impl FromSql<sql_types::Json, Mysql> for TicketSchemaDates {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<TicketSchemaDates>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for TicketSchemaDates {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+impl TicketSchemaDates {
+    pub fn is_valid(&self) -> bool {
+        self.0.iter().all(|(key, value)| {
+            !key.trim().is_empty()
+                && (parse_date(value).is_some()
+                    || FixedOffset::east_opt(0)
+                        .and_then(|timezone| parse_datetime(value, &timezone))
+                        .is_some())
+        })
+    }
+
+    pub async fn find_by_ticket(
+        conn: &mut crate::DbConn,
+        ticket_id: &i32,
+    ) -> Option<TicketSchemaDates> {
+        tickets::table
+            .inner_join(ticket_schemas::table)
+            .filter(tickets::id.eq(ticket_id))
+            .select(ticket_schemas::dates)
+            .first::<Option<TicketSchemaDates>>(conn)
+            .await
+            .ok()
+            .flatten()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FormDateError {
+    Invalid,
+    TooEarly,
+    TooLate,
+    Unavailable,
+}
+
+impl FormDateError {
+    pub fn i18n_key(&self) -> &'static str {
+        match self {
+            FormDateError::Invalid => "ticket.rules.invalid_date",
+            FormDateError::TooEarly => "ticket.rules.date_too_early",
+            FormDateError::TooLate => "ticket.rules.date_too_late",
+            FormDateError::Unavailable => "ticket.rules.date_bound_unavailable",
+        }
+    }
+}
+
+pub fn parse_timezone(timezone: &Option<String>) -> Option<FixedOffset> {
+    let timezone = match timezone {
+        Some(timezone) => timezone.trim(),
+        None => return FixedOffset::east_opt(0),
+    };
+    if timezone.is_empty() || timezone.eq_ignore_ascii_case("utc") || timezone == "Z" {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, rest) = if let Some(rest) = timezone.strip_prefix('+') {
+        (1, rest)
+    } else if let Some(rest) = timezone.strip_prefix('-') {
+        (-1, rest)
+    } else {
+        return None;
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if rest.len() == 4 && rest.is_ascii() => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let hours = hours.parse::<i32>().ok()?;
+    let minutes = minutes.parse::<i32>().ok()?;
+    if minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim(), DATE_FORMAT).ok()
+}
+
+pub fn parse_time(text: &str) -> Option<NaiveTime> {
+    let text = text.trim();
+    NaiveTime::parse_from_str(text, TIME_FORMAT)
+        .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+        .ok()
+}
+
+pub fn parse_datetime(text: &str, timezone: &FixedOffset) -> Option<DateTime<FixedOffset>> {
+    let text = text.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(text) {
+        return Some(datetime.with_timezone(timezone));
+    }
+
+    [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+    .and_then(|datetime| timezone.from_local_datetime(&datetime).single())
+}
+
+pub fn check_range<T: PartialOrd>(
+    value: T,
+    min: Option<T>,
+    max: Option<T>,
+) -> Result<T, FormDateError> {
+    if min.is_some_and(|min| value < min) {
+        return Err(FormDateError::TooEarly);
+    }
+    if max.is_some_and(|max| value > max) {
+        return Err(FormDateError::TooLate);
+    }
+    Ok(value)
+}
+
+pub fn resolve_bound<T>(
+    bound: &Option<FormFieldDateBound>,
+    convert: impl Fn(&FormFieldDateBound) -> Option<T>,
+) -> Result<Option<T>, FormDateError> {
+    match bound {
+        Some(bound) => convert(bound).map(Some).ok_or(FormDateError::Unavailable),
+        None => Ok(None),
+    }
+}
+
+impl FormFieldDateBound {
+    pub fn is_absolute(&self) -> bool {
+        matches!(self, FormFieldDateBound::Absolute { .. })
+    }
+
+    pub fn schema_date_key(&self) -> Option<&String> {
+        match self {
+            FormFieldDateBound::Schema { key, .. } => Some(key),
+            _ => None,
+        }
+    }
+
+    fn base(&self) -> Option<&str> {
+        match self {
+            FormFieldDateBound::Absolute { value } => Some(value),
+            FormFieldDateBound::Relative { from, .. } => match from {
+                FormFieldDefault::Static(FormFieldValue::String(value))
+                | FormFieldDefault::Dynamic {
+                    value: Some(FormFieldValue::String(value)),
+                    ..
+                } => Some(value),
+                _ => None,
+            },
+            FormFieldDateBound::Schema { .. } => None,
+        }
+    }
+
+    fn offset(&self) -> Duration {
+        match self {
+            FormFieldDateBound::Absolute { .. } => Duration::zero(),
+            FormFieldDateBound::Relative { days, minutes, .. }
+            | FormFieldDateBound::Schema { days, minutes, .. } => {
+                Duration::minutes(days * 24 * 60 + minutes)
+            }
+        }
+    }
+
+    pub async fn resolve(&self, conn: &mut crate::DbConn, ticket_id: &i32) -> FormFieldDateBound {
+        match self {
+            FormFieldDateBound::Relative {
+                from,
+                days,
+                minutes,
+            } => FormFieldDateBound::Relative {
+                from: from.resolve(conn, ticket_id).await,
+                days: *days,
+                minutes: *minutes,
+            },
+            FormFieldDateBound::Schema { .. } => {
+                match TicketSchemaDates::find_by_ticket(conn, ticket_id).await {
+                    Some(dates) => self.with_schema_dates(&dates),
+                    None => self.clone(),
+                }
+            }
+            FormFieldDateBound::Absolute { .. } => self.clone(),
+        }
+    }
+
+    pub fn with_schema_dates(&self, dates: &TicketSchemaDates) -> FormFieldDateBound {
+        match self {
+            FormFieldDateBound::Schema { key, days, minutes } => match dates.0.get(key) {
+                Some(value) => FormFieldDateBound::Relative {
+                    from: FormFieldDefault::Static(FormFieldValue::String(value.clone())),
+                    days: *days,
+                    minutes: *minutes,
+                },
+                None => self.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    pub fn to_date(&self) -> Option<NaiveDate> {
+        self.to_datetime(&FixedOffset::east_opt(0)?)
+            .map(|datetime| datetime.date_naive())
+    }
+
+    pub fn to_time(&self) -> Option<NaiveTime> {
+        let time = self.base().and_then(parse_time)?;
+        Some(time.overflowing_add_signed(self.offset()).0)
+    }
+
+    pub fn to_datetime(&self, timezone: &FixedOffset) -> Option<DateTime<FixedOffset>> {
+        let datetime = self.base().and_then(|base| {
+            parse_datetime(base, timezone).or_else(|| {
+                parse_date(base)
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .and_then(|datetime| timezone.from_local_datetime(&datetime).single())
+            })
+        })?;
+        datetime.checked_add_signed(self.offset())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate, TimeZone};
+
+    use super::{
+        check_range, parse_datetime, parse_timezone, resolve_bound, FormDateError,
+        FormFieldDateBound, TicketSchemaDates,
+    };
+    use crate::modules::ticket::forms::fields::{FormFieldDefault, FormFieldValue};
+
+    #[test]
+    fn parse_timezone_offsets() {
+        assert_eq!(parse_timezone(&None), FixedOffset::east_opt(0));
+        assert_eq!(
+            parse_timezone(&Some("UTC".to_owned())),
+            FixedOffset::east_opt(0)
+        );
+        assert_eq!(
+            parse_timezone(&Some("Z".to_owned())),
+            FixedOffset::east_opt(0)
+        );
+        assert_eq!(
+            parse_timezone(&Some("+08:00".to_owned())),
+            FixedOffset::east_opt(8 * 3600)
+        );
+        assert_eq!(
+            parse_timezone(&Some("-0530".to_owned())),
+            FixedOffset::east_opt(-(5 * 3600 + 30 * 60))
+        );
+        assert_eq!(
+            parse_timezone(&Some("+9".to_owned())),
+            FixedOffset::east_opt(9 * 3600)
+        );
+    }
+
+    #[test]
+    fn parse_timezone_rejects_invalid() {
+        assert_eq!(parse_timezone(&Some("Asia/Taipei".to_owned())), None);
+        assert_eq!(parse_timezone(&Some("08:00".to_owned())), None);
+        assert_eq!(parse_timezone(&Some("+08:60".to_owned())), None);
+        assert_eq!(parse_timezone(&Some("+25:00".to_owned())), None);
+    }
+
+    #[test]
+    fn parse_datetime_formats() {
+        let timezone = FixedOffset::east_opt(8 * 3600).unwrap();
+        let expected = timezone.with_ymd_and_hms(2024, 4, 1, 9, 30, 0).unwrap();
+        assert_eq!(
+            parse_datetime("2024-04-01T09:30:00", &timezone),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_datetime("2024-04-01 09:30", &timezone),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_datetime("2024-04-01T01:30:00Z", &timezone),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_datetime("2024-04-01T09:30:00+08:00", &timezone).map(|d| *d.offset()),
+            Some(timezone)
+        );
+    }
+
+    #[test]
+    fn parse_datetime_rejects_invalid() {
+        let timezone = FixedOffset::east_opt(0).unwrap();
+        assert_eq!(parse_datetime("2024-04-01", &timezone), None);
+        assert_eq!(parse_datetime("2024-02-30 10:00", &timezone), None);
+        assert_eq!(parse_datetime("tomorrow", &timezone), None);
+    }
+
+    #[test]
+    fn check_range_bounds() {
+        let min = NaiveDate::from_ymd_opt(2024, 4, 1);
+        let max = NaiveDate::from_ymd_opt(2024, 4, 30);
+        let value = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+        assert_eq!(check_range(value, min, max), Ok(value));
+        assert_eq!(
+            check_range(min.unwrap(), min, max),
+            min.ok_or(FormDateError::Invalid)
+        );
+        assert_eq!(
+            check_range(max.unwrap(), min, max),
+            max.ok_or(FormDateError::Invalid)
+        );
+        assert_eq!(
+            check_range(NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(), min, max),
+            Err(FormDateError::TooEarly)
+        );
+        assert_eq!(
+            check_range(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), min, max),
+            Err(FormDateError::TooLate)
+        );
+        assert_eq!(check_range(value, None, None), Ok(value));
+    }
+
+    #[test]
+    fn relative_bound_keeps_minute_offsets() {
+        let bound = FormFieldDateBound::Relative {
+            from: FormFieldDefault::Static(FormFieldValue::String("2024-04-01 23:00".to_owned())),
+            days: 0,
+            minutes: 90,
+        };
+        assert_eq!(bound.to_date(), NaiveDate::from_ymd_opt(2024, 4, 2));
+
+        let bound = FormFieldDateBound::Relative {
+            from: FormFieldDefault::Static(FormFieldValue::String("2024-04-01".to_owned())),
+            days: 1,
+            minutes: -30,
+        };
+        assert_eq!(bound.to_date(), NaiveDate::from_ymd_opt(2024, 4, 1));
+    }
+
+    #[test]
+    fn unresolved_bounds_are_unavailable() {
+        let bound = Some(FormFieldDateBound::Schema {
+            key: "deadline".to_owned(),
+            days: 0,
+            minutes: 0,
+        });
+        assert_eq!(
+            resolve_bound(&bound, |bound| bound.to_date()),
+            Err(FormDateError::Unavailable)
+        );
+        assert_eq!(resolve_bound(&None, |bound| bound.to_date()), Ok(None));
+
+        let dates = TicketSchemaDates(
+            [("deadline".to_owned(), "2024-04-30".to_owned())]
+                .into_iter()
+                .collect(),
+        );
+        let resolved = bound.map(|bound| bound.with_schema_dates(&dates));
+        assert_eq!(
+            resolve_bound(&resolved, |bound| bound.to_date()),
+            Ok(NaiveDate::from_ymd_opt(2024, 4, 30))
+        );
+    }
+}
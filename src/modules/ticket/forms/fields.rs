@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 
+use chrono::SecondsFormat;
 use diesel::{
     deserialize::FromSql,
     mysql::{Mysql, MysqlValue},
@@ -7,9 +8,16 @@ use diesel::{
     sql_types,
 };
 use rocket::serde::json::serde_json;
+use serde_json::Value;
 
 use crate::utils::{file::FileMime, image::ImageMime};
 
+use super::conditions::FormFieldCondition;
+use super::dates::{self, FormDateError, FormFieldDateBound, TicketSchemaDates};
+use super::models::TicketFormAnswer;
+use super::numbers::{self, FormNumberError};
+use super::texts::{self, FormTextError, FormTextPattern};
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(tag = "type", content = "content")]
 pub enum FormFieldDefault {
@@ -22,10 +30,78 @@ pub enum FormFieldDefault {
     },
 }
 
+impl FormFieldDefault {
+    pub fn value(&self) -> Option<&FormFieldValue> {
+        match self {
+            FormFieldDefault::Static(value) => Some(value),
+            FormFieldDefault::Dynamic { value, .. } => value.as_ref(),
+        }
+    }
+
+    pub fn is_in(&self, values: &[FormFieldValue]) -> bool {
+        match self {
+            FormFieldDefault::Static(value) => values.contains(value),
+            FormFieldDefault::Dynamic {
+                value: Some(value), ..
+            } => values.contains(value),
+            FormFieldDefault::Dynamic { value: None, .. } => false,
+        }
+    }
+
+    pub async fn resolve(&self, conn: &mut crate::DbConn, ticket_id: &i32) -> FormFieldDefault {
+        match self {
+            FormFieldDefault::Dynamic {
+                schema_form_id,
+                flow_id,
+                field_key,
+                ..
+            } => FormFieldDefault::Dynamic {
+                schema_form_id: *schema_form_id,
+                flow_id: *flow_id,
+                field_key: field_key.clone(),
+                value: TicketFormAnswer::get_field_value(
+                    conn,
+                    ticket_id,
+                    schema_form_id,
+                    flow_id,
+                    field_key,
+                )
+                .await,
+            },
+            FormFieldDefault::Static(_) => self.clone(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct FormFieldOption<T> {
     pub text: String,
+    pub text_zh: Option<String>,
+    pub text_en: Option<String>,
     pub value: T,
+    pub capacity: Option<u32>,
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<u32>,
+}
+
+impl<T> FormFieldOption<T> {
+    pub fn localized_text(&self, locale: &str) -> &String {
+        let text = if locale == "zh" {
+            &self.text_zh
+        } else {
+            &self.text_en
+        };
+        match text {
+            Some(text) if !text.trim().is_empty() => text,
+            _ => &self.text,
+        }
+    }
+
+    pub fn has_text(&self, text: &str) -> bool {
+        self.text == text
+            || self.text_zh.as_deref() == Some(text)
+            || self.text_en.as_deref() == Some(text)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -42,6 +118,95 @@ pub enum FormFieldValue {
     String(String),
     Bool(bool),
     Array(Vec<FormFieldOptionValue>),
+    Float(f64),
+    Other { other: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct FormChoiceOther {
+    pub text_zh: String,
+    pub text_en: String,
+    pub max_texts: u32,
+}
+
+impl FormChoiceOther {
+    pub fn text_of(value: &Value) -> Option<&str> {
+        value
+            .as_object()
+            .filter(|value| value.len() == 1)
+            .and_then(|value| value.get("other"))
+            .and_then(|value| value.as_str())
+    }
+
+    pub fn normalize(&self, text: &str) -> Result<Value, FormTextError> {
+        let text = text.trim();
+        texts::check_length(text, 1, self.max_texts, None, None)?;
+        Ok(serde_json::json!({ "other": text }))
+    }
+}
+
+pub fn waitlisted_value(value: &Value) -> Option<&Value> {
+    value
+        .as_object()
+        .filter(|value| value.len() == 1)
+        .and_then(|value| value.get("waitlist"))
+}
+
+impl FormFieldOptionValue {
+    pub fn matches(&self, value: &Value) -> bool {
+        match self {
+            FormFieldOptionValue::String(option) => value.as_str() == Some(option.as_str()),
+            FormFieldOptionValue::Integer(option) => value.as_i64() == Some(*option),
+        }
+    }
+}
+
+impl FormFieldValue {
+    pub fn from_answer(value: &Value) -> Option<FormFieldValue> {
+        if let Some(text) = FormChoiceOther::text_of(value) {
+            return Some(FormFieldValue::Other {
+                other: text.to_owned(),
+            });
+        }
+        if waitlisted_value(value).is_some() {
+            return None;
+        }
+        match value {
+            Value::Array(items) => Some(FormFieldValue::Array(
+                items
+                    .iter()
+                    .filter(|item| waitlisted_value(item).is_none())
+                    .filter_map(|item| match FormChoiceOther::text_of(item) {
+                        Some(text) => Some(FormFieldOptionValue::String(text.to_owned())),
+                        None => serde_json::from_value(item.clone()).ok(),
+                    })
+                    .collect(),
+            )),
+            _ => serde_json::from_value(value.clone()).ok(),
+        }
+    }
+
+    pub fn is_blank(&self) -> bool {
+        match self {
+            FormFieldValue::String(value) | FormFieldValue::Other { other: value } => {
+                value.trim().is_empty()
+            }
+            FormFieldValue::Array(values) => values.iter().all(|value| match value {
+                FormFieldOptionValue::String(value) => value.trim().is_empty(),
+                FormFieldOptionValue::Integer(_) => false,
+            }),
+            _ => false,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FormFieldValue::Integer(value) => Some(*value as f64),
+            FormFieldValue::Float(value) => Some(*value),
+            FormFieldValue::String(value) => value.trim().parse::<f64>().ok(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -50,6 +215,20 @@ pub enum FormTextFieldType {
     String,
     Email,
     Url,
+    Phone,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct FormGroupField<OV> {
+    pub key: String,
+    pub name_zh: String,
+    pub name_en: String,
+    #[serde(default)]
+    pub description_zh: String,
+    #[serde(default)]
+    pub description_en: String,
+    pub define: FormFieldDefine<OV>,
+    pub required: bool,
 }
 
 #[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -58,20 +237,38 @@ pub enum FormTextFieldType {
 pub enum FormFieldDefine<OV> {
     SingleLineText {
         text_type: Option<FormTextFieldType>,
+        #[serde(default)]
+        min_texts: u32,
         max_texts: u32,
+        min_words: Option<u32>,
+        max_words: Option<u32>,
+        pattern: Option<FormTextPattern>,
+        calling_code: Option<u16>,
         default: Option<FormFieldDefault>,
     },
     MultiLineText {
+        #[serde(default)]
+        min_texts: u32,
         max_texts: u32,
+        min_words: Option<u32>,
+        max_words: Option<u32>,
         max_lines: u32,
         default: Option<FormFieldDefault>,
     },
     SingleChoice {
         options: Vec<FormFieldOption<OV>>,
+        other: Option<FormChoiceOther>,
+        #[serde(default)]
+        waitlist: bool,
         default: Option<FormFieldDefault>,
     },
     MultipleChoice {
         options: Vec<FormFieldOption<OV>>,
+        other: Option<FormChoiceOther>,
+        #[serde(default)]
+        waitlist: bool,
+        #[serde(default)]
+        min_options: u32,
         max_options: u32,
         is_checkbox: bool,
         default: Option<FormFieldDefault>,
@@ -79,6 +276,15 @@ pub enum FormFieldDefine<OV> {
     Bool {
         default: Option<FormFieldDefault>,
     },
+    Number {
+        #[serde(default)]
+        decimals: u32,
+        min: Option<f64>,
+        max: Option<f64>,
+        step: Option<f64>,
+        unit: Option<String>,
+        default: Option<FormFieldDefault>,
+    },
     Image {
         max_size: u32,
         min_width: Option<u32>,
@@ -93,16 +299,548 @@ pub enum FormFieldDefine<OV> {
         mimes: Vec<FileMime>,
         default: Option<FormFieldDefault>,
     },
+    Date {
+        min: Option<FormFieldDateBound>,
+        max: Option<FormFieldDateBound>,
+        default: Option<FormFieldDefault>,
+    },
+    Time {
+        min: Option<FormFieldDateBound>,
+        max: Option<FormFieldDateBound>,
+        default: Option<FormFieldDefault>,
+    },
+    DateTime {
+        min: Option<FormFieldDateBound>,
+        max: Option<FormFieldDateBound>,
+        timezone: Option<String>,
+        default: Option<FormFieldDefault>,
+    },
+    Group {
+        min_items: u32,
+        max_items: u32,
+        fields: Vec<FormGroupField<OV>>,
+    },
     IfEqual {
         key: String,
         from: FormFieldDefault,
         value: Vec<FormFieldValue>,
     },
+    If {
+        key: String,
+        condition: FormFieldCondition,
+    },
     IfEnd {
         key: String,
     },
 }
 
+impl<OV> FormFieldDefine<OV> {
+    pub fn default_mut(&mut self) -> Option<&mut Option<FormFieldDefault>> {
+        match self {
+            FormFieldDefine::SingleLineText { default, .. }
+            | FormFieldDefine::MultiLineText { default, .. }
+            | FormFieldDefine::SingleChoice { default, .. }
+            | FormFieldDefine::MultipleChoice { default, .. }
+            | FormFieldDefine::Bool { default, .. }
+            | FormFieldDefine::Number { default, .. }
+            | FormFieldDefine::Image { default, .. }
+            | FormFieldDefine::File { default, .. }
+            | FormFieldDefine::Date { default, .. }
+            | FormFieldDefine::Time { default, .. }
+            | FormFieldDefine::DateTime { default, .. } => Some(default),
+            FormFieldDefine::Group { .. }
+            | FormFieldDefine::IfEqual { .. }
+            | FormFieldDefine::If { .. }
+            | FormFieldDefine::IfEnd { .. } => None,
+        }
+    }
+
+    pub fn value_type(&self) -> &'static str {
+        match self {
+            FormFieldDefine::SingleLineText { .. } | FormFieldDefine::MultiLineText { .. } => {
+                "string"
+            }
+            FormFieldDefine::SingleChoice { .. } => "choice",
+            FormFieldDefine::MultipleChoice { .. } => "choices",
+            FormFieldDefine::Bool { .. } => "bool",
+            FormFieldDefine::Number { .. } => "number",
+            FormFieldDefine::Image { .. } => "image",
+            FormFieldDefine::File { .. } => "file",
+            FormFieldDefine::Date { .. } => "date",
+            FormFieldDefine::Time { .. } => "time",
+            FormFieldDefine::DateTime { .. } => "datetime",
+            FormFieldDefine::Group { .. } => "group",
+            FormFieldDefine::IfEqual { .. }
+            | FormFieldDefine::If { .. }
+            | FormFieldDefine::IfEnd { .. } => "none",
+        }
+    }
+
+    pub fn localize(&mut self, locale: &str) {
+        match self {
+            FormFieldDefine::SingleChoice { options, .. }
+            | FormFieldDefine::MultipleChoice { options, .. } => {
+                for option in options.iter_mut() {
+                    option.text = option.localized_text(locale).clone();
+                }
+            }
+            FormFieldDefine::Group { fields, .. } => {
+                for field in fields.iter_mut() {
+                    field.define.localize(locale);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn schema_date_keys(&self) -> Vec<&String> {
+        match self {
+            FormFieldDefine::Date { min, max, .. }
+            | FormFieldDefine::Time { min, max, .. }
+            | FormFieldDefine::DateTime { min, max, .. } => [min, max]
+                .into_iter()
+                .flatten()
+                .filter_map(|bound| bound.schema_date_key())
+                .collect(),
+            FormFieldDefine::Group { fields, .. } => fields
+                .iter()
+                .flat_map(|field| field.define.schema_date_keys())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    pub fn resolve_schema_dates(&mut self, dates: &TicketSchemaDates) {
+        match self {
+            FormFieldDefine::Date { min, max, .. }
+            | FormFieldDefine::Time { min, max, .. }
+            | FormFieldDefine::DateTime { min, max, .. } => {
+                for bound in [min, max].into_iter().flatten() {
+                    *bound = bound.with_schema_dates(dates);
+                }
+            }
+            FormFieldDefine::Group { fields, .. } => {
+                for field in fields.iter_mut() {
+                    field.define.resolve_schema_dates(dates);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn condition(&self) -> Option<(&String, FormFieldCondition)> {
+        match self {
+            FormFieldDefine::IfEqual { key, from, value } => Some((
+                key,
+                FormFieldCondition::Equal {
+                    from: from.clone(),
+                    value: value.clone(),
+                },
+            )),
+            FormFieldDefine::If { key, condition } => Some((key, condition.clone())),
+            _ => None,
+        }
+    }
+}
+
+fn parse_option_text(
+    options: &[FormFieldOption<FormFieldOptionValue>],
+    other: &Option<FormChoiceOther>,
+    text: &str,
+) -> Value {
+    let option = options
+        .iter()
+        .find(|option| match &option.value {
+            FormFieldOptionValue::Integer(value) => value.to_string() == text,
+            FormFieldOptionValue::String(value) => value == text,
+        })
+        .or_else(|| options.iter().find(|option| option.has_text(text)));
+    match option {
+        Some(option) => serde_json::to_value(&option.value).unwrap_or_default(),
+        None if other.is_some() => serde_json::json!({ "other": text }),
+        None => Value::String(text.to_owned()),
+    }
+}
+
+impl FormFieldDefine<FormFieldOptionValue> {
+    pub fn option_label(&self, value: &Value, locale: &str) -> Option<String> {
+        let options = match self {
+            FormFieldDefine::SingleChoice { options, .. }
+            | FormFieldDefine::MultipleChoice { options, .. } => options,
+            _ => return None,
+        };
+        let label = |value: &Value| {
+            if let Some(text) = FormChoiceOther::text_of(value) {
+                return Some(text.to_owned());
+            }
+            let (value, suffix) = match waitlisted_value(value) {
+                Some(value) if locale == "zh" => (value, "（候補）"),
+                Some(value) => (value, " (waitlist)"),
+                None => (value, ""),
+            };
+            options
+                .iter()
+                .find(|option| option.value.matches(value))
+                .map(|option| format!("{}{}", option.localized_text(locale), suffix))
+        };
+        match value {
+            Value::Array(values) => Some(
+                values
+                    .iter()
+                    .filter_map(label)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ),
+            _ => label(value),
+        }
+    }
+
+    pub fn has_capacity(&self) -> bool {
+        match self {
+            FormFieldDefine::SingleChoice { options, .. }
+            | FormFieldDefine::MultipleChoice { options, .. } => {
+                options.iter().any(|option| option.capacity.is_some())
+            }
+            _ => false,
+        }
+    }
+
+    pub fn count_selections(&self, values: &[&Value]) -> Vec<u32> {
+        let options = match self {
+            FormFieldDefine::SingleChoice { options, .. }
+            | FormFieldDefine::MultipleChoice { options, .. } => options,
+            _ => return vec![],
+        };
+        options
+            .iter()
+            .map(|option| {
+                values
+                    .iter()
+                    .filter(|value| match value {
+                        Value::Array(items) => items.iter().any(|item| option.value.matches(item)),
+                        _ => option.value.matches(value),
+                    })
+                    .count() as u32
+            })
+            .collect()
+    }
+
+    pub fn fill_remaining(&mut self, values: &[&Value]) {
+        let counts = self.count_selections(values);
+        if let FormFieldDefine::SingleChoice { options, .. }
+        | FormFieldDefine::MultipleChoice { options, .. } = self
+        {
+            for (option, count) in options.iter_mut().zip(counts) {
+                option.remaining = option
+                    .capacity
+                    .map(|capacity| capacity.saturating_sub(count));
+            }
+        }
+    }
+
+    pub fn reserve_options(&self, value: &mut Value, values: &[&Value]) -> bool {
+        let (options, waitlist) = match self {
+            FormFieldDefine::SingleChoice {
+                options, waitlist, ..
+            }
+            | FormFieldDefine::MultipleChoice {
+                options, waitlist, ..
+            } => (options, *waitlist),
+            _ => return true,
+        };
+        let counts = self.count_selections(values);
+        let is_full = |item: &Value| {
+            options.iter().zip(counts.iter()).any(|(option, count)| {
+                option.value.matches(item)
+                    && option.capacity.is_some_and(|capacity| *count >= capacity)
+            })
+        };
+        let items = match value {
+            Value::Array(items) => items.iter_mut().collect::<Vec<_>>(),
+            value => vec![value],
+        };
+        for item in items {
+            if !is_full(&*item) {
+                continue;
+            }
+            if !waitlist {
+                return false;
+            }
+            *item = serde_json::json!({ "waitlist": item.take() });
+        }
+        true
+    }
+
+    pub fn promote_waitlist(&self, value: &mut Value, values: &[&Value]) -> bool {
+        let options = match self {
+            FormFieldDefine::SingleChoice { options, .. }
+            | FormFieldDefine::MultipleChoice { options, .. } => options,
+            _ => return false,
+        };
+        let mut counts = self.count_selections(values);
+        let items = match value {
+            Value::Array(items) => items.iter_mut().collect::<Vec<_>>(),
+            value => vec![value],
+        };
+        let mut promoted = false;
+        for item in items {
+            let waitlisted = match waitlisted_value(item) {
+                Some(waitlisted) => waitlisted.clone(),
+                None => continue,
+            };
+            let available = options
+                .iter()
+                .zip(counts.iter_mut())
+                .find_map(|(option, count)| {
+                    if !option.value.matches(&waitlisted) {
+                        return None;
+                    }
+                    if option.capacity.is_some_and(|capacity| *count >= capacity) {
+                        return Some(false);
+                    }
+                    *count += 1;
+                    Some(true)
+                });
+            if available.unwrap_or(false) {
+                *item = waitlisted;
+                promoted = true;
+            }
+        }
+        promoted
+    }
+
+    pub fn without_waitlist(&self, value: &Value) -> Value {
+        if !matches!(
+            self,
+            FormFieldDefine::SingleChoice { .. } | FormFieldDefine::MultipleChoice { .. }
+        ) {
+            return value.clone();
+        }
+        match value {
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| waitlisted_value(item).unwrap_or(item).clone())
+                    .collect(),
+            ),
+            _ => waitlisted_value(value).unwrap_or(value).clone(),
+        }
+    }
+
+    pub fn parse_text(&self, text: &str) -> Value {
+        let text = text.trim();
+        match self {
+            FormFieldDefine::SingleChoice { options, other, .. } => {
+                parse_option_text(options, other, text)
+            }
+            FormFieldDefine::MultipleChoice { options, other, .. } => Value::Array(
+                text.split(';')
+                    .map(|text| text.trim())
+                    .filter(|text| !text.is_empty())
+                    .map(|text| parse_option_text(options, other, text))
+                    .collect(),
+            ),
+            FormFieldDefine::Bool { .. } => match text.to_lowercase().as_str() {
+                "true" | "yes" | "1" => Value::Bool(true),
+                "false" | "no" | "0" => Value::Bool(false),
+                _ => Value::String(text.to_owned()),
+            },
+            FormFieldDefine::Number { .. } => text
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map_or(Value::String(text.to_owned()), Value::Number),
+            FormFieldDefine::Group { .. } => {
+                serde_json::from_str(text).unwrap_or(Value::String(text.to_owned()))
+            }
+            _ => Value::String(text.to_owned()),
+        }
+    }
+
+    pub fn other_text(&self, value: &Value) -> Option<String> {
+        if !matches!(
+            self,
+            FormFieldDefine::SingleChoice { other: Some(_), .. }
+                | FormFieldDefine::MultipleChoice { other: Some(_), .. }
+        ) {
+            return None;
+        }
+        match value {
+            Value::Array(values) => values
+                .iter()
+                .find_map(FormChoiceOther::text_of)
+                .map(|text| text.to_owned()),
+            _ => FormChoiceOther::text_of(value).map(|text| text.to_owned()),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            FormFieldDefine::SingleChoice { other, .. } => {
+                other.as_ref().is_none_or(|other| other.max_texts > 0)
+            }
+            FormFieldDefine::MultipleChoice {
+                other,
+                min_options,
+                max_options,
+                ..
+            } => {
+                min_options <= max_options && other.as_ref().is_none_or(|other| other.max_texts > 0)
+            }
+            FormFieldDefine::SingleLineText {
+                min_texts,
+                max_texts,
+                min_words,
+                max_words,
+                pattern,
+                calling_code,
+                ..
+            } => {
+                min_texts <= max_texts
+                    && texts::is_valid_word_range(*min_words, *max_words)
+                    && pattern
+                        .as_ref()
+                        .is_none_or(|pattern| pattern.compile().is_some())
+                    && calling_code.is_none_or(|code| code > 0 && code < 1000)
+            }
+            FormFieldDefine::MultiLineText {
+                min_texts,
+                max_texts,
+                min_words,
+                max_words,
+                ..
+            } => min_texts <= max_texts && texts::is_valid_word_range(*min_words, *max_words),
+            FormFieldDefine::Date { min, max, .. } => [min, max]
+                .into_iter()
+                .flatten()
+                .all(|bound| !bound.is_absolute() || bound.to_date().is_some()),
+            FormFieldDefine::Time { min, max, .. } => [min, max]
+                .into_iter()
+                .flatten()
+                .all(|bound| !bound.is_absolute() || bound.to_time().is_some()),
+            FormFieldDefine::DateTime {
+                min, max, timezone, ..
+            } => match dates::parse_timezone(timezone) {
+                Some(timezone) => [min, max]
+                    .into_iter()
+                    .flatten()
+                    .all(|bound| !bound.is_absolute() || bound.to_datetime(&timezone).is_some()),
+                None => false,
+            },
+            FormFieldDefine::Number {
+                decimals,
+                min,
+                max,
+                step,
+                ..
+            } => {
+                *decimals <= 10
+                    && step.is_none_or(|step| step > 0.0)
+                    && match (min, max) {
+                        (Some(min), Some(max)) => min <= max,
+                        _ => true,
+                    }
+            }
+            FormFieldDefine::Group {
+                min_items,
+                max_items,
+                fields,
+            } => {
+                min_items <= max_items
+                    && *max_items > 0
+                    && !fields.is_empty()
+                    && fields.iter().enumerate().all(|(index, field)| {
+                        !field.key.is_empty()
+                            && !fields[..index].iter().any(|other| other.key == field.key)
+                            && !matches!(
+                                field.define,
+                                FormFieldDefine::Group { .. }
+                                    | FormFieldDefine::IfEqual { .. }
+                                    | FormFieldDefine::If { .. }
+                                    | FormFieldDefine::IfEnd { .. }
+                            )
+                            && !field.define.has_capacity()
+                            && field.define.is_valid()
+                    })
+            }
+            _ => true,
+        }
+    }
+
+    pub fn normalize_number(&self, value: f64) -> Result<Value, FormNumberError> {
+        match self {
+            FormFieldDefine::Number {
+                decimals,
+                min,
+                max,
+                step,
+                ..
+            } => numbers::normalize(value, *decimals, *min, *max, *step),
+            _ => Err(FormNumberError::Invalid),
+        }
+    }
+
+    pub fn normalize_text(&self, text: &str) -> Result<String, FormTextError> {
+        match self {
+            FormFieldDefine::SingleLineText {
+                text_type,
+                pattern,
+                calling_code,
+                ..
+            } => {
+                let text = match text_type {
+                    Some(FormTextFieldType::Email) => texts::normalize_email(text)?,
+                    Some(FormTextFieldType::Url) => texts::normalize_url(text)?,
+                    Some(FormTextFieldType::Phone) => texts::normalize_phone(text, *calling_code)?,
+                    Some(FormTextFieldType::String) | None => text.to_owned(),
+                };
+                if let Some(pattern) = pattern {
+                    pattern.check(&text)?;
+                }
+                Ok(text)
+            }
+            _ => Ok(text.to_owned()),
+        }
+    }
+
+    pub fn normalize_date_text(&self, text: &str) -> Result<String, FormDateError> {
+        match self {
+            FormFieldDefine::Date { min, max, .. } => {
+                let date = dates::parse_date(text).ok_or(FormDateError::Invalid)?;
+                let date = dates::check_range(
+                    date,
+                    dates::resolve_bound(min, |min| min.to_date())?,
+                    dates::resolve_bound(max, |max| max.to_date())?,
+                )?;
+                Ok(date.format(dates::DATE_FORMAT).to_string())
+            }
+            FormFieldDefine::Time { min, max, .. } => {
+                let time = dates::parse_time(text).ok_or(FormDateError::Invalid)?;
+                let time = dates::check_range(
+                    time,
+                    dates::resolve_bound(min, |min| min.to_time())?,
+                    dates::resolve_bound(max, |max| max.to_time())?,
+                )?;
+                Ok(time.format(dates::TIME_FORMAT).to_string())
+            }
+            FormFieldDefine::DateTime {
+                min, max, timezone, ..
+            } => {
+                let timezone = dates::parse_timezone(timezone).ok_or(FormDateError::Invalid)?;
+                let datetime =
+                    dates::parse_datetime(text, &timezone).ok_or(FormDateError::Invalid)?;
+                let datetime = dates::check_range(
+                    datetime,
+                    dates::resolve_bound(min, |min| min.to_datetime(&timezone))?,
+                    dates::resolve_bound(max, |max| max.to_datetime(&timezone))?,
+                )?;
+                Ok(datetime.to_rfc3339_opts(SecondsFormat::Secs, true))
+            }
+            _ => Err(FormDateError::Invalid),
+        }
+    }
+}
+
 impl FromSql<sql_types::Json, Mysql> for FormFieldDefine<FormFieldOptionValue> {
     fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
         let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
@@ -129,4 +867,112 @@ pub struct FormSchemaField {
     pub define: FormFieldDefine<FormFieldOptionValue>,
     pub required: bool,
     pub editable: bool,
+    pub identifying: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::serde::json::serde_json::{self, json};
+
+    use super::{
+        FormFieldDefault, FormFieldDefine, FormFieldOption, FormFieldOptionValue, FormFieldValue,
+    };
+    use crate::modules::ticket::forms::conditions::FormFieldCondition;
+
+    fn option(value: &str, capacity: Option<u32>) -> FormFieldOption<FormFieldOptionValue> {
+        FormFieldOption {
+            text: value.to_owned(),
+            text_zh: None,
+            text_en: None,
+            value: FormFieldOptionValue::String(value.to_owned()),
+            capacity,
+            remaining: None,
+        }
+    }
+
+    fn single_choice(waitlist: bool) -> FormFieldDefine<FormFieldOptionValue> {
+        FormFieldDefine::SingleChoice {
+            options: vec![option("a", Some(1)), option("b", None)],
+            other: None,
+            waitlist,
+            default: None,
+        }
+    }
+
+    #[test]
+    fn count_selections_per_option() {
+        let define = single_choice(false);
+        let values = [json!("a"), json!("b"), json!(["a", "b"]), json!("c")];
+        let values = values.iter().collect::<Vec<_>>();
+        assert_eq!(define.count_selections(&values), vec![2, 2]);
+        assert_eq!(define.count_selections(&[]), vec![0, 0]);
+    }
+
+    #[test]
+    fn reserve_options_respects_capacity() {
+        let taken = json!("a");
+        let mut value = json!("b");
+        assert!(single_choice(false).reserve_options(&mut value, &[&taken]));
+        assert_eq!(value, json!("b"));
+
+        let mut value = json!("a");
+        assert!(!single_choice(false).reserve_options(&mut value, &[&taken]));
+
+        let mut value = json!("a");
+        assert!(single_choice(true).reserve_options(&mut value, &[&taken]));
+        assert_eq!(value, json!({ "waitlist": "a" }));
+
+        let mut value = json!("a");
+        assert!(single_choice(false).reserve_options(&mut value, &[]));
+        assert_eq!(value, json!("a"));
+    }
+
+    #[test]
+    fn promote_waitlist_when_room() {
+        let define = single_choice(true);
+        let mut value = json!({ "waitlist": "a" });
+        assert!(define.promote_waitlist(&mut value, &[]));
+        assert_eq!(value, json!("a"));
+
+        let taken = json!("a");
+        let mut value = json!({ "waitlist": "a" });
+        assert!(!define.promote_waitlist(&mut value, &[&taken]));
+        assert_eq!(value, json!({ "waitlist": "a" }));
+    }
+
+    #[test]
+    fn promote_waitlist_skips_missing_options() {
+        let define = single_choice(true);
+        let mut value = json!({ "waitlist": "removed" });
+        assert!(!define.promote_waitlist(&mut value, &[]));
+        assert_eq!(value, json!({ "waitlist": "removed" }));
+    }
+
+    #[test]
+    fn legacy_if_equal_condition() {
+        let define: FormFieldDefine<FormFieldOptionValue> = serde_json::from_value(json!({
+            "type": "IfEqual",
+            "key": "section",
+            "from": { "type": "Static", "content": "yes" },
+            "value": ["yes"]
+        }))
+        .unwrap();
+        let from = FormFieldDefault::Static(FormFieldValue::String("yes".to_owned()));
+        let expected = FormFieldCondition::Equal {
+            from,
+            value: vec![FormFieldValue::String("yes".to_owned())],
+        };
+        assert_eq!(
+            define.condition(),
+            Some((&"section".to_owned(), expected.clone()))
+        );
+
+        let define: FormFieldDefine<FormFieldOptionValue> = serde_json::from_value(json!({
+            "type": "If",
+            "key": "section",
+            "condition": serde_json::to_value(&expected).unwrap()
+        }))
+        .unwrap();
+        assert_eq!(define.condition(), Some((&"section".to_owned(), expected)));
+    }
 }
@@ -14,12 +14,22 @@ use crate::{
 };
 
 use self::{
+    dates::TicketSchemaDates,
     fields::{FormFieldDefault, FormFieldDefine},
-    models::{TicketFormFile, TicketFormImage, TicketSchemaForm, TicketSchemaFormField},
+    models::{
+        TicketFormAnswer, TicketFormFile, TicketFormImage, TicketSchemaForm, TicketSchemaFormField,
+    },
 };
 
+use super::models::Ticket;
+
+pub mod conditions;
+pub mod dates;
 pub mod fields;
 pub mod models;
+pub mod numbers;
+pub mod rules;
+pub mod texts;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FormSchema {
@@ -51,18 +61,44 @@ impl FormSchema {
         }
     }
 
+    pub fn resolve_schema_dates(&mut self, dates: &TicketSchemaDates) {
+        for field in self.fields.iter_mut() {
+            field.define.resolve_schema_dates(dates);
+        }
+    }
+
     pub async fn validate_and_normalize<'a>(
         &self,
         conn: &mut crate::DbConn,
         i18n: &I18n<'a>,
         data: &serde_json::Map<String, Value>
     ) -> Result<serde_json::Map<String, Value>, serde_json::Map<String, Value>> {
-        let FormSchema { fields, .. } = self;
+        self.validate(conn, i18n, data, false).await
+    }
+
+    pub async fn validate_draft<'a>(
+        &self,
+        conn: &mut crate::DbConn,
+        i18n: &I18n<'a>,
+        data: &serde_json::Map<String, Value>,
+    ) -> Result<serde_json::Map<String, Value>, serde_json::Map<String, Value>> {
+        self.validate(conn, i18n, data, true).await
+    }
+
+    async fn validate<'a>(
+        &self,
+        conn: &mut crate::DbConn,
+        i18n: &I18n<'a>,
+        data: &serde_json::Map<String, Value>,
+        partial: bool,
+    ) -> Result<serde_json::Map<String, Value>, serde_json::Map<String, Value>> {
+        let FormSchema { form, fields } = self;
 
         let mut is_error = false;
         let mut result = serde_json::Map::new();
         let mut errors = serde_json::Map::new();
 
+        let mut visible_keys: Vec<String> = vec![];
         let mut last_falsy_if: Option<String> = None;
         for field in fields.iter() {
             if let Some(ref falsy_if_key) = last_falsy_if {
@@ -77,29 +113,17 @@ impl FormSchema {
                 }
                 continue;
             }
-            if let TicketSchemaFormField {
-                define: FormFieldDefine::IfEqual { key, from, value },
-                ..
-            } = field.clone()
-            {
-                let condition_result = match from {
-                    FormFieldDefault::Static(from_value) => value.contains(&from_value),
-                    FormFieldDefault::Dynamic {
-                        value: from_value, ..
-                    } => match from_value {
-                        Some(from_value) => value.contains(&from_value),
-                        None => false,
-                    },
-                };
-                if !condition_result {
-                    last_falsy_if = Some(key);
+            if let Some((key, condition)) = field.define.condition() {
+                if !condition.is_met() {
+                    last_falsy_if = Some(key.clone());
                 }
                 continue;
             }
+            visible_keys.push(field.key.clone());
             let user_value = match data.get::<String>(&field.key) {
                 Some(value) => value,
                 None => {
-                    if field.required {
+                    if field.required && !partial {
                         is_error = true;
                         errors.insert(
                             field.key.clone(),
@@ -109,7 +133,23 @@ impl FormSchema {
                     continue;
                 }
             };
-            let new_value = match field.validate_and_normalize(conn, i18n, user_value).await {
+            if let FormFieldDefine::Group { .. } = field.define {
+                match field.validate_group(conn, i18n, user_value, partial).await {
+                    Ok(serde_json::Value::Null) => (),
+                    Ok(value) => {
+                        result.insert(field.key.clone(), value);
+                    }
+                    Err(group_errors) => {
+                        is_error = true;
+                        errors.extend(group_errors);
+                    }
+                }
+                continue;
+            }
+            let new_value = match field
+                .validate_and_normalize(conn, i18n, user_value, partial)
+                .await
+            {
                 Ok(value) => value,
                 Err(err) => {
                     is_error = true;
@@ -123,6 +163,15 @@ impl FormSchema {
             result.insert(field.key.clone(), new_value);
         }
 
+        if let Some(rules) = form.rules.as_ref().filter(|_| !partial) {
+            for (key, message) in rules.check(i18n, &result, &visible_keys) {
+                if !errors.contains_key(&key) {
+                    is_error = true;
+                    errors.insert(key, message);
+                }
+            }
+        }
+
         if is_error {
             Err(errors)
         } else {
@@ -130,11 +179,110 @@ impl FormSchema {
         }
     }
 
+    pub fn has_capacity(&self) -> bool {
+        self.fields.iter().any(|field| field.define.has_capacity())
+    }
+
+    pub fn fill_remaining(&mut self, answers: &[Value]) {
+        for field in self.fields.iter_mut() {
+            let values = answers
+                .iter()
+                .filter_map(|answer| answer.get(&field.key))
+                .collect::<Vec<_>>();
+            field.define.fill_remaining(&values);
+        }
+    }
+
+    pub fn reserve_options<'a>(
+        &self,
+        i18n: &I18n<'a>,
+        answers: &[Value],
+        data: &mut serde_json::Map<String, Value>,
+    ) -> Result<(), serde_json::Map<String, Value>> {
+        let mut errors = serde_json::Map::new();
+        for field in self.fields.iter() {
+            let value = match data.get_mut(&field.key) {
+                Some(value) => value,
+                None => continue,
+            };
+            let values = answers
+                .iter()
+                .filter_map(|answer| answer.get(&field.key))
+                .collect::<Vec<_>>();
+            if !field.define.reserve_options(value, &values) {
+                errors.insert(
+                    field.key.clone(),
+                    Value::String(
+                        i18n.tf("ticket.rules.option_full", &[("field", field.key.clone())]),
+                    ),
+                );
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub async fn reserve_capacity<'a>(
+        &self,
+        conn: &mut crate::DbConn,
+        i18n: &I18n<'a>,
+        ticket_id: i32,
+        data: &mut serde_json::Map<String, Value>,
+    ) -> Result<Result<(), serde_json::Map<String, Value>>, diesel::result::Error> {
+        if !self.has_capacity() {
+            return Ok(Ok(()));
+        }
+        self.form.lock(conn).await?;
+        let answers =
+            TicketFormAnswer::get_finished_values(conn, self.form.id, Some(ticket_id)).await?;
+        Ok(self.reserve_options(i18n, &answers, data))
+    }
+
+    pub async fn promote_waitlist(
+        &self,
+        conn: &mut crate::DbConn,
+    ) -> Result<(), diesel::result::Error> {
+        if !self.has_capacity() {
+            return Ok(());
+        }
+        self.form.lock(conn).await?;
+        let mut answers = TicketFormAnswer::get_finished(conn, self.form.id).await?;
+        for index in 0..answers.len() {
+            let mut promoted = false;
+            for field in self.fields.iter() {
+                if !field.define.has_capacity() {
+                    continue;
+                }
+                let mut value = match answers[index].value.get(&field.key) {
+                    Some(value) => value.clone(),
+                    None => continue,
+                };
+                let values = answers
+                    .iter()
+                    .filter_map(|answer| answer.value.get(&field.key))
+                    .collect::<Vec<_>>();
+                if field.define.promote_waitlist(&mut value, &values) {
+                    answers[index].value[&field.key] = value;
+                    promoted = true;
+                }
+            }
+            if promoted {
+                answers[index].updated_at = chrono::Utc::now().naive_utc();
+                answers[index].save(conn).await?;
+            }
+        }
+        Ok(())
+    }
+
     pub async fn get_processed_fields(
         conn: &mut crate::DbConn,
         fields: Vec<TicketSchemaFormField>,
         ticket_id: &i32,
     ) -> Vec<TicketSchemaFormField> {
+        let prefill = Ticket::get_prefill(conn, ticket_id).await;
         let mut result: Vec<TicketSchemaFormField> = vec![];
         let mut last_falsy_if: Option<String> = None;
         for raw_field in fields.iter() {
@@ -153,22 +301,17 @@ impl FormSchema {
             let mut field = raw_field.clone();
             let new_define = field.get_define_with_default_value(conn, ticket_id).await;
             field.define = new_define;
-            if let TicketSchemaFormField {
-                define: FormFieldDefine::IfEqual { key, from, value },
-                ..
-            } = field.clone()
+            if let Some(value) = prefill
+                .as_ref()
+                .and_then(|prefill| prefill.get(field.ticket_schema_form_id, &field.key))
             {
-                let condition_result = match from {
-                    FormFieldDefault::Static(from_value) => value.contains(&from_value),
-                    FormFieldDefault::Dynamic {
-                        value: from_value, ..
-                    } => match from_value {
-                        Some(from_value) => value.contains(&from_value),
-                        None => false,
-                    },
-                };
-                if !condition_result {
-                    last_falsy_if = Some(key);
+                if let Some(default) = field.define.default_mut() {
+                    *default = Some(FormFieldDefault::Static(value.clone()));
+                }
+            }
+            if let Some((key, condition)) = field.define.condition() {
+                if !condition.is_met() {
+                    last_falsy_if = Some(key.clone());
                 }
                 continue;
             }
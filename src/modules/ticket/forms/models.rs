@@ -5,9 +5,10 @@ use rocket::serde::json::serde_json;
 use rocket_db_pools::diesel::prelude::RunQueryDsl;
 use serde_json::Value;
 
+use crate::models::user::User;
 use crate::modules::ticket::models::{TicketFlow, TicketSchemaFlow};
 use crate::schema::{
-    ticket_flows, ticket_form_answers, ticket_form_files, ticket_form_images,
+    ticket_flows, ticket_form_answers, ticket_form_drafts, ticket_form_files, ticket_form_images,
     ticket_schema_form_fields, ticket_schema_forms, tickets,
 };
 use crate::utils::file::FileMime;
@@ -19,8 +20,12 @@ use crate::utils::{
 };
 
 use super::fields::{
-    FormFieldDefault, FormFieldDefine, FormFieldOptionValue, FormFieldValue, FormSchemaField,
+    FormChoiceOther, FormFieldDefine, FormFieldOptionValue, FormFieldValue, FormGroupField,
+    FormSchemaField,
 };
+use super::numbers::FormNumberError;
+use super::rules::FormRules;
+use super::texts::{self, FormTextError};
 use super::{FormSchema, PartFormSchema};
 
 #[derive(
@@ -47,6 +52,7 @@ pub struct TicketSchemaForm {
     pub created_at: NaiveDateTime,
     #[serde(with = "unix_time")]
     pub updated_at: NaiveDateTime,
+    pub rules: Option<FormRules>,
 }
 
 impl TicketSchemaForm {
@@ -54,11 +60,13 @@ impl TicketSchemaForm {
         conn: &mut crate::DbConn,
         schema_flow: &TicketSchemaFlow,
         expired_at: Option<NaiveDateTime>,
+        rules: Option<FormRules>,
     ) -> Result<TicketSchemaForm, diesel::result::Error> {
         diesel::insert_into(ticket_schema_forms::table)
             .values((
                 ticket_schema_forms::ticket_schema_flow_id.eq(schema_flow.id),
                 ticket_schema_forms::expired_at.eq(expired_at),
+                ticket_schema_forms::rules.eq(rules),
             ))
             .execute(conn)
             .await?;
@@ -86,6 +94,18 @@ impl TicketSchemaForm {
         Ok(FormSchema { form, fields })
     }
 
+    pub async fn lock(
+        &self,
+        conn: &mut crate::DbConn,
+    ) -> Result<TicketSchemaForm, diesel::result::Error> {
+        ticket_schema_forms::table
+            .find(self.id)
+            .for_update()
+            .select(TicketSchemaForm::as_select())
+            .first(conn)
+            .await
+    }
+
     pub async fn find_with_field(
         conn: &mut crate::DbConn,
         id: i32,
@@ -180,6 +200,7 @@ impl TicketSchemaForm {
                     ticket_schema_form_fields::define.eq(field.define),
                     ticket_schema_form_fields::required.eq(field.required),
                     ticket_schema_form_fields::editable.eq(field.editable),
+                    ticket_schema_form_fields::identifying.eq(field.identifying),
                 )
             })
             .collect::<Vec<_>>();
@@ -223,19 +244,205 @@ pub struct TicketSchemaFormField {
     pub updated_at: NaiveDateTime,
     pub name_en: String,
     pub description_en: String,
+    #[serde(default)]
+    pub identifying: bool,
 }
 
 impl TicketSchemaFormField {
+    fn with_group_field(
+        &self,
+        group_field: &FormGroupField<FormFieldOptionValue>,
+    ) -> TicketSchemaFormField {
+        TicketSchemaFormField {
+            key: group_field.key.clone(),
+            name_zh: group_field.name_zh.clone(),
+            name_en: group_field.name_en.clone(),
+            description_zh: group_field.description_zh.clone(),
+            description_en: group_field.description_en.clone(),
+            define: group_field.define.clone(),
+            required: group_field.required,
+            editable: true,
+            ..self.clone()
+        }
+    }
+
+    pub fn find_group_field(&self, key: &str) -> Option<TicketSchemaFormField> {
+        match &self.define {
+            FormFieldDefine::Group { fields, .. } => fields
+                .iter()
+                .find(|field| field.key == key)
+                .map(|field| self.with_group_field(field)),
+            _ => None,
+        }
+    }
+
+    pub async fn validate_group<'a>(
+        &self,
+        conn: &mut crate::DbConn,
+        i18n: &I18n<'a>,
+        data: &serde_json::Value,
+        partial: bool,
+    ) -> Result<Value, serde_json::Map<String, Value>> {
+        let mut errors = serde_json::Map::new();
+        let (min_items, max_items, fields) = match &self.define {
+            FormFieldDefine::Group {
+                min_items,
+                max_items,
+                fields,
+            } => (*min_items, *max_items, fields),
+            _ => {
+                errors.insert(
+                    self.key.clone(),
+                    Value::String(i18n.tf("ticket.rules.unknown", &[("field", self.key.clone())])),
+                );
+                return Err(errors);
+            }
+        };
+        if !self.editable {
+            return Ok(serde_json::Value::Null);
+        }
+
+        let items = match data {
+            serde_json::Value::Null => vec![],
+            serde_json::Value::Array(items) => items.clone(),
+            _ => {
+                errors.insert(
+                    self.key.clone(),
+                    Value::String(i18n.tf("ticket.rules.unknown", &[("field", self.key.clone())])),
+                );
+                return Err(errors);
+            }
+        };
+
+        let items_len = items.len() as u32;
+        if items_len == 0 && self.required && !partial {
+            errors.insert(
+                self.key.clone(),
+                Value::String(i18n.tf("ticket.rules.required", &[("field", self.key.clone())])),
+            );
+        } else if items_len < min_items && !partial {
+            errors.insert(
+                self.key.clone(),
+                Value::String(
+                    i18n.tf("ticket.rules.too_few_items", &[("field", self.key.clone())]),
+                ),
+            );
+        } else if items_len > max_items {
+            errors.insert(
+                self.key.clone(),
+                Value::String(i18n.tf(
+                    "ticket.rules.too_many_items",
+                    &[("field", self.key.clone())],
+                )),
+            );
+        }
+
+        let mut result = vec![];
+        for (index, item) in items.iter().enumerate() {
+            let item = match item.as_object() {
+                Some(item) => item,
+                None => {
+                    errors.insert(
+                        format!("{}.{}", self.key, index),
+                        Value::String(
+                            i18n.tf("ticket.rules.unknown", &[("field", self.key.clone())]),
+                        ),
+                    );
+                    continue;
+                }
+            };
+            let mut normalized = serde_json::Map::new();
+            for group_field in fields.iter() {
+                let field = self.with_group_field(group_field);
+                let error_key = format!("{}.{}.{}", self.key, index, field.key);
+                let value =
+                    match item.get(&field.key) {
+                        Some(value) => value,
+                        None => {
+                            if field.required && !partial {
+                                errors.insert(
+                                    error_key,
+                                    Value::String(i18n.tf(
+                                        "ticket.rules.required",
+                                        &[("field", field.key.clone())],
+                                    )),
+                                );
+                            }
+                            continue;
+                        }
+                    };
+                match field
+                    .validate_and_normalize(conn, i18n, value, partial)
+                    .await
+                {
+                    Ok(serde_json::Value::Null) => (),
+                    Ok(value) => {
+                        normalized.insert(field.key.clone(), value);
+                    }
+                    Err(err) => {
+                        errors.insert(error_key, Value::String(err));
+                    }
+                }
+            }
+            result.push(Value::Object(normalized));
+        }
+
+        if errors.is_empty() {
+            Ok(Value::Array(result))
+        } else {
+            Err(errors)
+        }
+    }
+
     pub async fn validate_and_normalize<'a>(
         &self,
         conn: &mut crate::DbConn,
         i18n: &I18n<'a>,
         data: &serde_json::Value,
+        partial: bool,
+    ) -> Result<Value, String> {
+        let value = self.normalize_value(i18n, data, partial)?;
+        let id = match (&self.define, &value) {
+            (
+                FormFieldDefine::File { .. } | FormFieldDefine::Image { .. },
+                serde_json::Value::String(id),
+            ) => id.split('.').next().unwrap_or_default().to_owned(),
+            _ => return Ok(value),
+        };
+        match self.define {
+            FormFieldDefine::File { .. } => {
+                if let Err(_) = TicketFormFile::find(conn, id).await {
+                    return Err(i18n.tf(
+                        "ticket.rules.not_upload_file",
+                        &[("field", self.key.clone())],
+                    ));
+                }
+            }
+            _ => {
+                if let Err(_) = TicketFormImage::find(conn, id).await {
+                    return Err(i18n.tf(
+                        "ticket.rules.not_upload_image",
+                        &[("field", self.key.clone())],
+                    ));
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    pub fn normalize_value<'a>(
+        &self,
+        i18n: &I18n<'a>,
+        data: &serde_json::Value,
+        partial: bool,
     ) -> Result<Value, String> {
         if !self.editable {
             return Ok(serde_json::Value::Null);
         }
 
+        let required = self.required && !partial;
+        let data = &self.define.without_waitlist(data);
+
         fn is_same(left: &FormFieldOptionValue, right: &serde_json::Value) -> bool {
             match left {
                 FormFieldOptionValue::String(lv) => {
@@ -254,7 +461,7 @@ impl TicketSchemaFormField {
 
         match data {
             serde_json::Value::Null => {
-                if self.required {
+                if required {
                     return Err(i18n.tf("ticket.rules.required", &[("field", self.key.clone())]));
                 } else {
                     return Ok(data.clone());
@@ -269,35 +476,70 @@ impl TicketSchemaFormField {
             }
             serde_json::Value::String(value) => {
                 match self.define {
-                    FormFieldDefine::SingleLineText { max_texts, .. } => {
+                    FormFieldDefine::SingleLineText {
+                        min_texts,
+                        max_texts,
+                        min_words,
+                        max_words,
+                        ref pattern,
+                        ..
+                    } => {
                         let text = value.trim();
-                        let text_len = text.len() as u32;
-                        if text_len == 0 && self.required {
-                            return Err(
-                                i18n.tf("ticket.rules.required", &[("field", self.key.clone())])
-                            );
+                        if text.is_empty() {
+                            if required {
+                                return Err(i18n
+                                    .tf("ticket.rules.required", &[("field", self.key.clone())]));
+                            }
+                            return Ok(serde_json::Value::String(text.to_owned()));
                         }
-                        if text_len > max_texts {
-                            return Err(i18n
-                                .tf("ticket.rules.text_too_long", &[("field", self.key.clone())]));
+                        if let Err(err) = texts::check_length(
+                            text,
+                            if partial { 0 } else { min_texts },
+                            max_texts,
+                            min_words.filter(|_| !partial),
+                            max_words,
+                        ) {
+                            return Err(i18n.tf(err.i18n_key(), &[("field", self.key.clone())]));
                         }
-                        Ok(serde_json::Value::String(text.to_owned()))
+                        self.define
+                            .normalize_text(text)
+                            .map(serde_json::Value::String)
+                            .map_err(|err| {
+                                let message = match (&err, pattern) {
+                                    (FormTextError::PatternMismatch, Some(pattern)) => {
+                                        pattern.message(i18n.locale)
+                                    }
+                                    _ => None,
+                                };
+                                message.unwrap_or_else(|| {
+                                    i18n.tf(err.i18n_key(), &[("field", self.key.clone())])
+                                })
+                            })
                     }
                     FormFieldDefine::MultiLineText {
+                        min_texts,
                         max_texts,
+                        min_words,
+                        max_words,
                         max_lines,
                         ..
                     } => {
                         let text = value.trim();
-                        let text_len = text.len() as u32;
-                        if text_len == 0 && self.required {
-                            return Err(
-                                i18n.tf("ticket.rules.required", &[("field", self.key.clone())])
-                            );
+                        if text.is_empty() {
+                            if required {
+                                return Err(i18n
+                                    .tf("ticket.rules.required", &[("field", self.key.clone())]));
+                            }
+                            return Ok(serde_json::Value::String(text.to_owned()));
                         }
-                        if text_len > max_texts {
-                            return Err(i18n
-                                .tf("ticket.rules.text_too_long", &[("field", self.key.clone())]));
+                        if let Err(err) = texts::check_length(
+                            text,
+                            if partial { 0 } else { min_texts },
+                            max_texts,
+                            min_words.filter(|_| !partial),
+                            max_words,
+                        ) {
+                            return Err(i18n.tf(err.i18n_key(), &[("field", self.key.clone())]));
                         }
                         if text.count_words("\n") > max_lines {
                             return Err(i18n.tf(
@@ -316,36 +558,42 @@ impl TicketSchemaFormField {
                         }
                         return Ok(data.clone());
                     }
-                    FormFieldDefine::File { .. } => {
-                        let mut id = value.clone();
-                        if id.contains(".") {
-                            id = id.split('.').collect::<Vec<&str>>()[0].to_owned();
-                        }
-                        if let Err(_) = TicketFormFile::find(conn, id).await {
-                            return Err(i18n.tf(
-                                "ticket.rules.not_upload_file",
-                                &[("field", self.key.clone())],
-                            ));
-                        }
+                    FormFieldDefine::File { .. } | FormFieldDefine::Image { .. } => {
                         return Ok(data.clone());
                     }
-                    FormFieldDefine::Image { .. } => {
-                        let mut id = value.clone();
-                        if id.contains(".") {
-                            id = id.split('.').collect::<Vec<&str>>()[0].to_owned();
+                    FormFieldDefine::Number { .. } => {
+                        let text = value.trim();
+                        if text.is_empty() {
+                            if required {
+                                return Err(i18n
+                                    .tf("ticket.rules.required", &[("field", self.key.clone())]));
+                            }
+                            return Ok(serde_json::Value::Null);
                         }
-                        if let Err(_) = TicketFormImage::find(conn, id).await {
-                            return Err(i18n.tf(
-                                "ticket.rules.not_upload_image",
-                                &[("field", self.key.clone())],
-                            ));
+                        text.parse::<f64>()
+                            .map_err(|_| FormNumberError::Invalid)
+                            .and_then(|value| self.define.normalize_number(value))
+                            .map_err(|err| i18n.tf(err.i18n_key(), &[("field", self.key.clone())]))
+                    }
+                    FormFieldDefine::Date { .. }
+                    | FormFieldDefine::Time { .. }
+                    | FormFieldDefine::DateTime { .. } => {
+                        if value.trim().is_empty() {
+                            if required {
+                                return Err(i18n
+                                    .tf("ticket.rules.required", &[("field", self.key.clone())]));
+                            }
+                            return Ok(serde_json::Value::Null);
                         }
-                        return Ok(data.clone());
+                        self.define
+                            .normalize_date_text(value)
+                            .map(serde_json::Value::String)
+                            .map_err(|err| i18n.tf(err.i18n_key(), &[("field", self.key.clone())]))
                     }
                     _ => Err(i18n.tf("ticket.rules.unknown", &[("field", self.key.clone())])),
                 }
             }
-            serde_json::Value::Number(_) => match self.define {
+            serde_json::Value::Number(value) => match self.define {
                 FormFieldDefine::SingleChoice { ref options, .. } => {
                     if !options.iter().any(|o| is_same(&o.value, data)) {
                         return Err(i18n.tf(
@@ -355,41 +603,81 @@ impl TicketSchemaFormField {
                     }
                     return Ok(data.clone());
                 }
+                FormFieldDefine::Number { .. } => value
+                    .as_f64()
+                    .ok_or(FormNumberError::Invalid)
+                    .and_then(|value| self.define.normalize_number(value))
+                    .map_err(|err| i18n.tf(err.i18n_key(), &[("field", self.key.clone())])),
                 _ => Err(i18n.tf("ticket.rules.unknown", &[("field", self.key.clone())])),
             },
             serde_json::Value::Array(value) => {
                 if let FormFieldDefine::MultipleChoice {
                     ref options,
+                    ref other,
+                    min_options,
                     max_options,
                     ..
                 } = self.define
                 {
                     let value_len = value.len() as u32;
-                    if value_len == 0 && self.required {
+                    if value_len == 0 && required {
                         return Err(
                             i18n.tf("ticket.rules.required", &[("field", self.key.clone())])
                         );
                     }
-                    if value_len > max_options {
+                    if value_len < min_options && !partial {
                         return Err(i18n.tf(
-                            "ticket.rules.too_many_choice",
+                            "ticket.rules.too_few_choice",
                             &[("field", self.key.clone())],
                         ));
                     }
-                    if !value
-                        .iter()
-                        .all(|v| options.iter().any(|o| is_same(&o.value, v)))
-                    {
+                    if value_len > max_options {
                         return Err(i18n.tf(
-                            "ticket.rules.not_valid_choice",
+                            "ticket.rules.too_many_choice",
                             &[("field", self.key.clone())],
                         ));
                     }
-                    return Ok(data.clone());
+                    let mut result = vec![];
+                    let mut has_other = false;
+                    for v in value.iter() {
+                        if options.iter().any(|o| is_same(&o.value, v)) {
+                            result.push(v.clone());
+                            continue;
+                        }
+                        match (other, FormChoiceOther::text_of(v)) {
+                            (Some(other), Some(text)) if !has_other => {
+                                has_other = true;
+                                result.push(other.normalize(text).map_err(|err| {
+                                    i18n.tf(err.i18n_key(), &[("field", self.key.clone())])
+                                })?);
+                            }
+                            _ => {
+                                return Err(i18n.tf(
+                                    "ticket.rules.not_valid_choice",
+                                    &[("field", self.key.clone())],
+                                ));
+                            }
+                        }
+                    }
+                    return Ok(serde_json::Value::Array(result));
                 }
                 Err(i18n.tf("ticket.rules.unknown", &[("field", self.key.clone())]))
             }
-            _ => return Err(i18n.tf("ticket.rules.unknown", &[("field", self.key.clone())])),
+            serde_json::Value::Object(_) => match self.define {
+                FormFieldDefine::SingleChoice {
+                    other: Some(ref other),
+                    ..
+                } => match FormChoiceOther::text_of(data) {
+                    Some(text) => other
+                        .normalize(text)
+                        .map_err(|err| i18n.tf(err.i18n_key(), &[("field", self.key.clone())])),
+                    None => Err(i18n.tf(
+                        "ticket.rules.not_valid_choice",
+                        &[("field", self.key.clone())],
+                    )),
+                },
+                _ => Err(i18n.tf("ticket.rules.unknown", &[("field", self.key.clone())])),
+            },
         }
     }
 
@@ -398,262 +686,34 @@ impl TicketSchemaFormField {
         conn: &mut crate::DbConn,
         ticket_id: &i32,
     ) -> FormFieldDefine<FormFieldOptionValue> {
-        match self.define.clone() {
-            FormFieldDefine::SingleLineText {
-                max_texts,
-                text_type,
-                default,
-            } => {
-                let new_default = match default {
-                    Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key,
-                        ..
-                    }) => Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key: field_key.clone(),
-                        value: TicketFormAnswer::get_field_value(
-                            conn,
-                            ticket_id,
-                            &schema_form_id,
-                            &flow_id,
-                            &field_key,
-                        )
-                        .await,
-                    }),
-                    _ => default,
-                };
-                FormFieldDefine::SingleLineText {
-                    default: new_default,
-                    text_type,
-                    max_texts,
-                }
-            }
-            FormFieldDefine::MultiLineText {
-                max_texts,
-                max_lines,
-                default,
-            } => {
-                let new_default = match default {
-                    Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key,
-                        ..
-                    }) => Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key: field_key.clone(),
-                        value: TicketFormAnswer::get_field_value(
-                            conn,
-                            ticket_id,
-                            &schema_form_id,
-                            &flow_id,
-                            &field_key,
-                        )
-                        .await,
-                    }),
-                    _ => default,
-                };
-                FormFieldDefine::MultiLineText {
-                    default: new_default,
-                    max_texts,
-                    max_lines,
-                }
-            }
-            FormFieldDefine::SingleChoice {
-                options, default, ..
-            } => {
-                let new_default = match default {
-                    Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key,
-                        ..
-                    }) => Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key: field_key.clone(),
-                        value: TicketFormAnswer::get_field_value(
-                            conn,
-                            ticket_id,
-                            &schema_form_id,
-                            &flow_id,
-                            &field_key,
-                        )
-                        .await,
-                    }),
-                    _ => default,
-                };
-                FormFieldDefine::SingleChoice {
-                    default: new_default,
-                    options,
-                }
-            }
-            FormFieldDefine::MultipleChoice {
-                default,
-                options,
-                max_options,
-                is_checkbox,
-            } => {
-                let new_default = match default {
-                    Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key,
-                        ..
-                    }) => Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key: field_key.clone(),
-                        value: TicketFormAnswer::get_field_value(
-                            conn,
-                            ticket_id,
-                            &schema_form_id,
-                            &flow_id,
-                            &field_key,
-                        )
-                        .await,
-                    }),
-                    _ => default,
-                };
-                FormFieldDefine::MultipleChoice {
-                    default: new_default,
-                    options,
-                    max_options,
-                    is_checkbox,
-                }
-            }
-            FormFieldDefine::Bool { default } => {
-                let new_default = match default {
-                    Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key,
-                        ..
-                    }) => Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key: field_key.clone(),
-                        value: TicketFormAnswer::get_field_value(
-                            conn,
-                            ticket_id,
-                            &schema_form_id,
-                            &flow_id,
-                            &field_key,
-                        )
-                        .await,
-                    }),
-                    _ => default.clone(),
-                };
-                FormFieldDefine::Bool {
-                    default: new_default,
-                }
+        let mut define = self.define.clone();
+        match &mut define {
+            FormFieldDefine::IfEqual { from, .. } => {
+                *from = from.resolve(conn, ticket_id).await;
             }
-            FormFieldDefine::Image {
-                default,
-                max_size,
-                min_width,
-                max_width,
-                min_height,
-                max_height,
-                mimes,
-            } => {
-                let new_default = match default {
-                    Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key,
-                        ..
-                    }) => Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key: field_key.clone(),
-                        value: TicketFormAnswer::get_field_value(
-                            conn,
-                            ticket_id,
-                            &schema_form_id,
-                            &flow_id,
-                            &field_key,
-                        )
-                        .await,
-                    }),
-                    _ => default,
-                };
-                FormFieldDefine::Image {
-                    default: new_default,
-                    max_size,
-                    min_width,
-                    max_width,
-                    min_height,
-                    max_height,
-                    mimes,
-                }
+            FormFieldDefine::If { condition, .. } => {
+                *condition = condition.resolve(conn, ticket_id).await;
             }
-            FormFieldDefine::File {
-                default,
-                max_size,
-                mimes,
-            } => {
-                let new_default = match default {
-                    Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key,
-                        ..
-                    }) => Some(FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key: field_key.clone(),
-                        value: TicketFormAnswer::get_field_value(
-                            conn,
-                            ticket_id,
-                            &schema_form_id,
-                            &flow_id,
-                            &field_key,
-                        )
-                        .await,
-                    }),
-                    _ => default,
-                };
-                FormFieldDefine::File {
-                    default: new_default,
-                    max_size,
-                    mimes,
+            FormFieldDefine::Date { min, max, .. }
+            | FormFieldDefine::Time { min, max, .. }
+            | FormFieldDefine::DateTime { min, max, .. } => {
+                for bound in [min, max].into_iter().flatten() {
+                    *bound = bound.resolve(conn, ticket_id).await;
                 }
             }
-            FormFieldDefine::IfEqual { key, from, value } => {
-                let new_from = match from {
-                    FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key,
-                        ..
-                    } => FormFieldDefault::Dynamic {
-                        schema_form_id,
-                        flow_id,
-                        field_key: field_key.clone(),
-                        value: TicketFormAnswer::get_field_value(
-                            conn,
-                            ticket_id,
-                            &schema_form_id,
-                            &flow_id,
-                            &field_key,
-                        )
-                        .await,
-                    },
-                    FormFieldDefault::Static(_) => from.clone(),
-                };
-                FormFieldDefine::IfEqual {
-                    key,
-                    from: new_from,
-                    value,
+            FormFieldDefine::Group { fields, .. } => {
+                for group_field in fields.iter_mut() {
+                    let field = self.with_group_field(group_field);
+                    group_field.define =
+                        Box::pin(field.get_define_with_default_value(conn, ticket_id)).await;
                 }
             }
-            _ => self.define.clone(),
+            _ => (),
         }
+        if let Some(Some(default)) = define.default_mut() {
+            *default = default.resolve(conn, ticket_id).await;
+        }
+        define
     }
 
     pub async fn get_results(
@@ -682,6 +742,26 @@ impl TicketSchemaFormField {
                     "height": image.height
                 });
             }
+        } else if let FormFieldDefine::Group { fields, .. } = &self.define {
+            if let serde_json::Value::Array(items) = data {
+                let mut result = vec![];
+                for item in items {
+                    let mut item = item.clone();
+                    if let serde_json::Value::Object(values) = &mut item {
+                        for group_field in fields.iter() {
+                            let field = self.with_group_field(group_field);
+                            if let Some(value) = values.get_mut(&field.key) {
+                                *value = Box::pin(TicketSchemaFormField::get_results(
+                                    &field, conn, value,
+                                ))
+                                .await;
+                            }
+                        }
+                    }
+                    result.push(item);
+                }
+                return serde_json::Value::Array(result);
+            }
         }
         data.clone()
     }
@@ -810,6 +890,64 @@ impl TicketFormAnswer {
         }
     }
 
+    pub async fn find_by_flow(
+        conn: &mut crate::DbConn,
+        flow: &TicketFlow,
+    ) -> Result<Option<TicketFormAnswer>, diesel::result::Error> {
+        TicketFormAnswer::belonging_to(flow)
+            .select(TicketFormAnswer::as_select())
+            .first(conn)
+            .await
+            .optional()
+    }
+
+    pub async fn get_schema_answers(
+        conn: &mut crate::DbConn,
+        schema_id: i32,
+    ) -> Result<Vec<TicketFormAnswer>, diesel::result::Error> {
+        ticket_form_answers::table
+            .inner_join(ticket_flows::table.inner_join(tickets::table))
+            .filter(tickets::ticket_schema_id.eq(schema_id))
+            .filter(ticket_flows::skipped.eq(false))
+            .select(TicketFormAnswer::as_select())
+            .load(conn)
+            .await
+    }
+
+    pub async fn get_finished_values(
+        conn: &mut crate::DbConn,
+        schema_form_id: i32,
+        exclude_ticket_id: Option<i32>,
+    ) -> Result<Vec<Value>, diesel::result::Error> {
+        let mut query = ticket_form_answers::table
+            .inner_join(ticket_flows::table)
+            .filter(ticket_form_answers::ticket_schema_form_id.eq(schema_form_id))
+            .filter(ticket_flows::finished.eq(true))
+            .filter(ticket_flows::skipped.eq(false))
+            .into_boxed();
+
+        if let Some(ticket_id) = exclude_ticket_id {
+            query = query.filter(ticket_flows::ticket_id.ne(ticket_id));
+        }
+
+        query.select(ticket_form_answers::value).load(conn).await
+    }
+
+    pub async fn get_finished(
+        conn: &mut crate::DbConn,
+        schema_form_id: i32,
+    ) -> Result<Vec<TicketFormAnswer>, diesel::result::Error> {
+        ticket_form_answers::table
+            .inner_join(ticket_flows::table)
+            .filter(ticket_form_answers::ticket_schema_form_id.eq(schema_form_id))
+            .filter(ticket_flows::finished.eq(true))
+            .filter(ticket_flows::skipped.eq(false))
+            .order(ticket_form_answers::id.asc())
+            .select(TicketFormAnswer::as_select())
+            .load(conn)
+            .await
+    }
+
     pub async fn get_field_value(
         conn: &mut crate::DbConn,
         ticket_id: &i32,
@@ -835,13 +973,10 @@ impl TicketFormAnswer {
             .await;
 
         match answer {
-            Ok(answer) => match answer.value.get(field_key) {
-                Some(value) => match serde_json::from_value::<FormFieldValue>(value.clone()) {
-                    Ok(value) => Some(value),
-                    Err(_) => None,
-                },
-                None => None,
-            },
+            Ok(answer) => answer
+                .value
+                .get(field_key)
+                .and_then(FormFieldValue::from_answer),
             Err(_) => None,
         }
     }
@@ -868,6 +1003,128 @@ impl TicketFormAnswer {
     }
 }
 
+#[derive(
+    Queryable,
+    Identifiable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Insertable,
+    AsChangeset,
+)]
+#[diesel(belongs_to(TicketFlow))]
+#[diesel(belongs_to(TicketSchemaForm))]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = ticket_form_drafts)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct TicketFormDraft {
+    pub id: i32,
+    pub ticket_flow_id: i32,
+    pub ticket_schema_form_id: i32,
+    pub user_id: String,
+    pub value: serde_json::Value,
+    #[serde(with = "unix_time")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "unix_time")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl TicketFormDraft {
+    pub async fn save_or_create(
+        conn: &mut crate::DbConn,
+        flow: &TicketFlow,
+        form_schema: &FormSchema,
+        user: &User,
+        value: serde_json::Map<String, Value>,
+    ) -> Result<TicketFormDraft, diesel::result::Error> {
+        let FormSchema { form, .. } = form_schema;
+
+        let form_draft: Result<TicketFormDraft, _> = ticket_form_drafts::table
+            .filter(ticket_form_drafts::ticket_flow_id.eq(flow.id))
+            .filter(ticket_form_drafts::ticket_schema_form_id.eq(form.id))
+            .filter(ticket_form_drafts::user_id.eq(&user.id))
+            .first(conn)
+            .await;
+
+        match form_draft {
+            Ok(mut form_draft) => {
+                form_draft.value = serde_json::Value::Object(value);
+                form_draft.save(conn).await?;
+                Ok(form_draft)
+            }
+            Err(diesel::result::Error::NotFound) => {
+                diesel::insert_into(ticket_form_drafts::table)
+                    .values((
+                        ticket_form_drafts::ticket_flow_id.eq(flow.id),
+                        ticket_form_drafts::ticket_schema_form_id.eq(form.id),
+                        ticket_form_drafts::user_id.eq(user.id.clone()),
+                        ticket_form_drafts::value.eq(serde_json::Value::Object(value)),
+                    ))
+                    .execute(conn)
+                    .await?;
+
+                sql_function! {
+                    fn last_insert_id() -> Integer;
+                }
+
+                ticket_form_drafts::table
+                    .find(last_insert_id())
+                    .first(conn)
+                    .await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn find_by_flow(
+        conn: &mut crate::DbConn,
+        flow: &TicketFlow,
+        user: &User,
+    ) -> Result<Option<TicketFormDraft>, diesel::result::Error> {
+        TicketFormDraft::belonging_to(flow)
+            .filter(ticket_form_drafts::user_id.eq(&user.id))
+            .select(TicketFormDraft::as_select())
+            .first(conn)
+            .await
+            .optional()
+    }
+
+    pub async fn delete_flow_drafts(
+        conn: &mut crate::DbConn,
+        flow: &TicketFlow,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(
+            ticket_form_drafts::table.filter(ticket_form_drafts::ticket_flow_id.eq(flow.id)),
+        )
+        .execute(conn)
+        .await
+    }
+
+    pub async fn save(&self, conn: &mut crate::DbConn) -> Result<usize, diesel::result::Error> {
+        match diesel::replace_into(ticket_form_drafts::table)
+            .values(self)
+            .execute(conn)
+            .await
+        {
+            Ok(result) => Ok(result),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                _,
+            )) => {
+                diesel::update(ticket_form_drafts::table)
+                    .filter(ticket_form_drafts::id.eq(&self.id))
+                    .set(self)
+                    .execute(conn)
+                    .await
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
 #[derive(
     Queryable,
     Identifiable,
@@ -990,3 +1247,118 @@ impl TicketFormFile {
         ticket_form_files::table.find(id).first(conn).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rocket::serde::json::serde_json::json;
+
+    use super::TicketSchemaFormField;
+    use crate::modules::ticket::forms::fields::{
+        FormFieldDefine, FormFieldOption, FormFieldOptionValue,
+    };
+    use crate::utils::i18n::I18n;
+
+    fn field(define: FormFieldDefine<FormFieldOptionValue>) -> TicketSchemaFormField {
+        TicketSchemaFormField {
+            id: 1,
+            ticket_schema_form_id: 1,
+            order: 1,
+            key: "field".to_owned(),
+            name_zh: String::new(),
+            description_zh: String::new(),
+            define,
+            required: true,
+            editable: true,
+            created_at: Default::default(),
+            updated_at: Default::default(),
+            name_en: String::new(),
+            description_en: String::new(),
+            identifying: false,
+        }
+    }
+
+    fn text_field() -> TicketSchemaFormField {
+        field(FormFieldDefine::SingleLineText {
+            text_type: None,
+            min_texts: 5,
+            max_texts: 10,
+            min_words: Some(2),
+            max_words: None,
+            pattern: None,
+            calling_code: None,
+            default: None,
+        })
+    }
+
+    fn choice_field() -> TicketSchemaFormField {
+        let options = ["a", "b", "c"]
+            .into_iter()
+            .map(|value| FormFieldOption {
+                text: value.to_owned(),
+                text_zh: None,
+                text_en: None,
+                value: FormFieldOptionValue::String(value.to_owned()),
+                capacity: None,
+                remaining: None,
+            })
+            .collect();
+        field(FormFieldDefine::MultipleChoice {
+            options,
+            other: None,
+            waitlist: false,
+            min_options: 2,
+            max_options: 2,
+            is_checkbox: true,
+            default: None,
+        })
+    }
+
+    #[test]
+    fn partial_allows_missing_required_values() {
+        let i18n = I18n::new("en");
+        let field = text_field();
+        assert!(field.normalize_value(&i18n, &json!(null), false).is_err());
+        assert!(field.normalize_value(&i18n, &json!(""), false).is_err());
+        assert_eq!(
+            field.normalize_value(&i18n, &json!(null), true),
+            Ok(json!(null))
+        );
+        assert_eq!(
+            field.normalize_value(&i18n, &json!(" "), true),
+            Ok(json!(""))
+        );
+    }
+
+    #[test]
+    fn partial_skips_minimum_limits() {
+        let i18n = I18n::new("en");
+        let field = text_field();
+        assert!(field.normalize_value(&i18n, &json!("abc"), false).is_err());
+        assert_eq!(
+            field.normalize_value(&i18n, &json!("abc"), true),
+            Ok(json!("abc"))
+        );
+
+        let field = choice_field();
+        assert!(field.normalize_value(&i18n, &json!(["a"]), false).is_err());
+        assert_eq!(
+            field.normalize_value(&i18n, &json!(["a"]), true),
+            Ok(json!(["a"]))
+        );
+    }
+
+    #[test]
+    fn partial_keeps_maximum_limits() {
+        let i18n = I18n::new("en");
+        let field = text_field();
+        assert!(field
+            .normalize_value(&i18n, &json!("far too long text"), true)
+            .is_err());
+
+        let field = choice_field();
+        assert!(field
+            .normalize_value(&i18n, &json!(["a", "b", "c"]), true)
+            .is_err());
+        assert!(field.normalize_value(&i18n, &json!(["x"]), true).is_err());
+    }
+}
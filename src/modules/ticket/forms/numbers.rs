@@ -0,0 +1,202 @@
+use rocket::serde::json::serde_json;
+use serde_json::Value;
+
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug, PartialEq)]
+pub enum FormNumberError {
+    Invalid,
+    TooSmall,
+    TooLarge,
+    NotOnStep,
+    TooManyDecimals,
+}
+
+impl FormNumberError {
+    pub fn i18n_key(&self) -> &'static str {
+        match self {
+            FormNumberError::Invalid => "ticket.rules.invalid_number",
+            FormNumberError::TooSmall => "ticket.rules.number_too_small",
+            FormNumberError::TooLarge => "ticket.rules.number_too_large",
+            FormNumberError::NotOnStep => "ticket.rules.number_not_on_step",
+            FormNumberError::TooManyDecimals => "ticket.rules.number_too_many_decimals",
+        }
+    }
+}
+
+fn is_integral(value: f64) -> bool {
+    (value - value.round()).abs() <= EPSILON * value.abs().max(1.0)
+}
+
+pub fn normalize(
+    value: f64,
+    decimals: u32,
+    min: Option<f64>,
+    max: Option<f64>,
+    step: Option<f64>,
+) -> Result<Value, FormNumberError> {
+    if !value.is_finite() {
+        return Err(FormNumberError::Invalid);
+    }
+
+    let factor = 10f64.powi(decimals as i32);
+    if !is_integral(value * factor) {
+        return Err(FormNumberError::TooManyDecimals);
+    }
+    let value = (value * factor).round() / factor;
+
+    if min.is_some_and(|min| value < min) {
+        return Err(FormNumberError::TooSmall);
+    }
+    if max.is_some_and(|max| value > max) {
+        return Err(FormNumberError::TooLarge);
+    }
+    if let Some(step) = step.filter(|step| *step > 0.0) {
+        if !is_integral((value - min.unwrap_or(0.0)) / step) {
+            return Err(FormNumberError::NotOnStep);
+        }
+    }
+
+    if decimals == 0 {
+        if value < i64::MIN as f64 {
+            return Err(FormNumberError::TooSmall);
+        }
+        if value >= i64::MAX as f64 {
+            return Err(FormNumberError::TooLarge);
+        }
+        return Ok(Value::from(value as i64));
+    }
+    serde_json::Number::from_f64(value)
+        .map(Value::Number)
+        .ok_or(FormNumberError::Invalid)
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct FormNumberSummary {
+    pub schema_form_id: i32,
+    pub key: String,
+    pub unit: Option<String>,
+    pub count: usize,
+    pub sum: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub average: Option<f64>,
+}
+
+impl FormNumberSummary {
+    pub fn new(schema_form_id: i32, key: String, unit: Option<String>, values: Vec<f64>) -> Self {
+        let count = values.len();
+        let sum = values.iter().sum::<f64>();
+        FormNumberSummary {
+            schema_form_id,
+            key,
+            unit,
+            count,
+            sum,
+            min: values.iter().cloned().reduce(f64::min),
+            max: values.iter().cloned().reduce(f64::max),
+            average: if count > 0 {
+                Some(sum / count as f64)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::serde::json::serde_json::json;
+
+    use super::{normalize, FormNumberError, FormNumberSummary};
+
+    #[test]
+    fn normalize_integers() {
+        assert_eq!(normalize(42.0, 0, None, None, None), Ok(json!(42)));
+        assert_eq!(
+            normalize(-3.0, 0, Some(-5.0), Some(5.0), None),
+            Ok(json!(-3))
+        );
+        assert_eq!(
+            normalize(1.5, 0, None, None, None),
+            Err(FormNumberError::TooManyDecimals)
+        );
+    }
+
+    #[test]
+    fn normalize_decimals() {
+        assert_eq!(normalize(1.25, 2, None, None, None), Ok(json!(1.25)));
+        assert_eq!(normalize(0.1 + 0.2, 1, None, None, None), Ok(json!(0.3)));
+        assert_eq!(
+            normalize(1.255, 2, None, None, None),
+            Err(FormNumberError::TooManyDecimals)
+        );
+    }
+
+    #[test]
+    fn normalize_bounds_and_step() {
+        assert_eq!(
+            normalize(0.0, 0, Some(1.0), None, None),
+            Err(FormNumberError::TooSmall)
+        );
+        assert_eq!(
+            normalize(11.0, 0, None, Some(10.0), None),
+            Err(FormNumberError::TooLarge)
+        );
+        assert_eq!(normalize(7.0, 0, Some(1.0), None, Some(3.0)), Ok(json!(7)));
+        assert_eq!(
+            normalize(6.0, 0, Some(1.0), None, Some(3.0)),
+            Err(FormNumberError::NotOnStep)
+        );
+        assert_eq!(normalize(0.75, 2, None, None, Some(0.25)), Ok(json!(0.75)));
+    }
+
+    #[test]
+    fn normalize_rejects_out_of_range() {
+        assert_eq!(
+            normalize(f64::NAN, 0, None, None, None),
+            Err(FormNumberError::Invalid)
+        );
+        assert_eq!(
+            normalize(f64::INFINITY, 2, None, None, None),
+            Err(FormNumberError::Invalid)
+        );
+        assert_eq!(
+            normalize(1e19, 0, None, None, None),
+            Err(FormNumberError::TooLarge)
+        );
+        assert_eq!(
+            normalize(-1e19, 0, None, None, None),
+            Err(FormNumberError::TooSmall)
+        );
+        assert_eq!(
+            normalize(9.2e18, 0, None, None, None),
+            Ok(json!(9_200_000_000_000_000_000i64))
+        );
+    }
+
+    #[test]
+    fn summary_values() {
+        let summary = FormNumberSummary::new(
+            1,
+            "amount".to_owned(),
+            Some("TWD".to_owned()),
+            vec![3.0, 1.0, 2.0],
+        );
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.sum, 6.0);
+        assert_eq!(summary.min, Some(1.0));
+        assert_eq!(summary.max, Some(3.0));
+        assert_eq!(summary.average, Some(2.0));
+    }
+
+    #[test]
+    fn summary_without_values() {
+        let summary = FormNumberSummary::new(1, "amount".to_owned(), None, vec![]);
+        assert_eq!(summary.count, 0);
+        assert_eq!(summary.sum, 0.0);
+        assert_eq!(summary.min, None);
+        assert_eq!(summary.max, None);
+        assert_eq!(summary.average, None);
+    }
+}
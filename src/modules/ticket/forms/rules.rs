@@ -0,0 +1,379 @@
+use std::cmp::Ordering;
+
+use chrono::DateTime;
+use diesel::{
+    deserialize::FromSql,
+    mysql::{Mysql, MysqlValue},
+    serialize::ToSql,
+    sql_types,
+};
+use rocket::serde::json::serde_json;
+use serde_json::{Map, Value};
+
+use crate::utils::i18n::I18n;
+
+use super::conditions::FormCompareOperator;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(tag = "type")]
+pub enum FormRule {
+    Compare {
+        key: String,
+        operator: FormCompareOperator,
+        other: String,
+    },
+    AtLeastOne {
+        keys: Vec<String>,
+    },
+    Sum {
+        keys: Vec<String>,
+        operator: FormCompareOperator,
+        other: String,
+    },
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::Json)]
+pub struct FormRules(pub Vec<FormRule>);
+
+impl FromSql<sql_types::Json, Mysql> for FormRules {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<FormRules>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for FormRules {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+fn field_key(key: &str) -> &str {
+    key.split('.').next().unwrap_or(key)
+}
+
+fn lookup<'v>(values: &'v Map<String, Value>, key: &str) -> Vec<&'v Value> {
+    match key.split_once('.') {
+        Some((group, item_key)) => match values.get(group) {
+            Some(Value::Array(items)) => {
+                items.iter().filter_map(|item| item.get(item_key)).collect()
+            }
+            _ => vec![],
+        },
+        None => values.get(key).into_iter().collect(),
+    }
+}
+
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(value) => value.trim().is_empty(),
+        Value::Array(values) => values.is_empty(),
+        _ => false,
+    }
+}
+
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    if let (Some(left), Some(right)) = (left.as_f64(), right.as_f64()) {
+        return left.partial_cmp(&right);
+    }
+    let (left, right) = (left.as_str()?, right.as_str()?);
+    if let (Ok(left), Ok(right)) = (
+        DateTime::parse_from_rfc3339(left),
+        DateTime::parse_from_rfc3339(right),
+    ) {
+        return Some(left.cmp(&right));
+    }
+    Some(left.cmp(right))
+}
+
+fn operator_i18n_key(operator: &FormCompareOperator) -> &'static str {
+    match operator {
+        FormCompareOperator::Gt => "ticket.rules.must_be_greater",
+        FormCompareOperator::Gte => "ticket.rules.must_be_at_least",
+        FormCompareOperator::Lt => "ticket.rules.must_be_less",
+        FormCompareOperator::Lte => "ticket.rules.must_be_at_most",
+    }
+}
+
+impl FormRule {
+    pub fn keys(&self) -> Vec<&String> {
+        match self {
+            FormRule::Compare { key, other, .. } => vec![key, other],
+            FormRule::AtLeastOne { keys } => keys.iter().collect(),
+            FormRule::Sum { keys, other, .. } => keys.iter().chain([other]).collect(),
+        }
+    }
+
+    pub fn check<'a>(
+        &self,
+        i18n: &I18n<'a>,
+        values: &Map<String, Value>,
+        visible_keys: &[String],
+    ) -> Vec<(String, String)> {
+        let is_visible = |key: &String| visible_keys.iter().any(|k| k == field_key(key));
+        match self {
+            FormRule::Compare {
+                key,
+                operator,
+                other,
+            } => {
+                if !is_visible(key) || !is_visible(other) {
+                    return vec![];
+                }
+                let others = lookup(values, other);
+                let other_value = match others.first() {
+                    Some(value) if !is_blank(value) => *value,
+                    _ => return vec![],
+                };
+                let is_failed = lookup(values, key)
+                    .into_iter()
+                    .filter(|value| !is_blank(value))
+                    .any(|value| {
+                        compare(value, other_value)
+                            .is_none_or(|ordering| !operator.accepts(ordering))
+                    });
+                if !is_failed {
+                    return vec![];
+                }
+                vec![(
+                    field_key(key).to_owned(),
+                    i18n.tf(
+                        operator_i18n_key(operator),
+                        &[("field", key.clone()), ("other", other.clone())],
+                    ),
+                )]
+            }
+            FormRule::AtLeastOne { keys } => {
+                let keys = keys
+                    .iter()
+                    .filter(|key| is_visible(key))
+                    .collect::<Vec<_>>();
+                let is_filled = keys.iter().any(|key| {
+                    lookup(values, key)
+                        .into_iter()
+                        .any(|value| !is_blank(value))
+                });
+                if keys.is_empty() || is_filled {
+                    return vec![];
+                }
+                let fields = keys
+                    .iter()
+                    .map(|key| key.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                keys.iter()
+                    .map(|key| {
+                        (
+                            field_key(key).to_owned(),
+                            i18n.tf("ticket.rules.at_least_one", &[("fields", fields.clone())]),
+                        )
+                    })
+                    .collect()
+            }
+            FormRule::Sum {
+                keys,
+                operator,
+                other,
+            } => {
+                let first_key = match keys.first() {
+                    Some(key) if is_visible(key) && is_visible(other) => key,
+                    _ => return vec![],
+                };
+                let limit = match lookup(values, other)
+                    .first()
+                    .and_then(|value| value.as_f64())
+                {
+                    Some(limit) => limit,
+                    None => return vec![],
+                };
+                let sum = keys
+                    .iter()
+                    .flat_map(|key| lookup(values, key))
+                    .filter_map(|value| value.as_f64())
+                    .sum::<f64>();
+                match sum.partial_cmp(&limit) {
+                    Some(ordering) if operator.accepts(ordering) => vec![],
+                    _ => vec![(
+                        field_key(first_key).to_owned(),
+                        i18n.tf(
+                            "ticket.rules.sum_out_of_range",
+                            &[("fields", keys.join(", ")), ("other", other.clone())],
+                        ),
+                    )],
+                }
+            }
+        }
+    }
+}
+
+impl FormRules {
+    pub fn is_valid(&self, field_keys: &[String]) -> bool {
+        self.0.iter().all(|rule| {
+            let keys = rule.keys();
+            !keys.is_empty()
+                && keys
+                    .iter()
+                    .all(|key| field_keys.iter().any(|k| k == field_key(key)))
+        })
+    }
+
+    pub fn check<'a>(
+        &self,
+        i18n: &I18n<'a>,
+        values: &Map<String, Value>,
+        visible_keys: &[String],
+    ) -> Map<String, Value> {
+        let mut errors = Map::new();
+        for rule in self.0.iter() {
+            for (key, message) in rule.check(i18n, values, visible_keys) {
+                errors.entry(key).or_insert(Value::String(message));
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::serde::json::serde_json::{self, json, Map, Value};
+
+    use super::{FormRule, FormRules};
+    use crate::utils::i18n::I18n;
+
+    fn rules(value: Value) -> FormRules {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn values(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    fn keys(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn compare_values() {
+        let i18n = I18n::new("en");
+        let rules = rules(json!([
+            { "type": "Compare", "key": "end", "operator": "gt", "other": "start" }
+        ]));
+        let visible = keys(&["start", "end"]);
+
+        let errors = rules.check(&i18n, &values(json!({ "start": 3, "end": 2 })), &visible);
+        assert_eq!(errors.keys().collect::<Vec<_>>(), ["end"]);
+        assert!(rules
+            .check(&i18n, &values(json!({ "start": 3, "end": 4 })), &visible)
+            .is_empty());
+
+        let dates = json!({
+            "start": "2024-04-01T10:00:00+08:00",
+            "end": "2024-04-01T03:00:00Z"
+        });
+        assert!(rules.check(&i18n, &values(dates), &visible).is_empty());
+    }
+
+    #[test]
+    fn compare_skips_blank_and_hidden_fields() {
+        let i18n = I18n::new("en");
+        let rules = rules(json!([
+            { "type": "Compare", "key": "end", "operator": "gt", "other": "start" }
+        ]));
+        let data = values(json!({ "start": 3, "end": 2 }));
+        assert!(rules.check(&i18n, &data, &keys(&["end"])).is_empty());
+        assert!(rules
+            .check(
+                &i18n,
+                &values(json!({ "start": "", "end": 2 })),
+                &keys(&["start", "end"])
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn compare_group_items() {
+        let i18n = I18n::new("en");
+        let rules = rules(json!([
+            { "type": "Compare", "key": "items.amount", "operator": "lte", "other": "limit" }
+        ]));
+        let data = values(json!({
+            "limit": 10,
+            "items": [{ "amount": 5 }, { "amount": 12 }]
+        }));
+        let errors = rules.check(&i18n, &data, &keys(&["items", "limit"]));
+        assert_eq!(errors.keys().collect::<Vec<_>>(), ["items"]);
+    }
+
+    #[test]
+    fn at_least_one_value() {
+        let i18n = I18n::new("en");
+        let rules = rules(json!([{ "type": "AtLeastOne", "keys": ["phone", "email"] }]));
+        let visible = keys(&["phone", "email"]);
+
+        let errors = rules.check(&i18n, &values(json!({ "phone": " " })), &visible);
+        assert_eq!(errors.keys().collect::<Vec<_>>(), ["email", "phone"]);
+        assert!(rules
+            .check(&i18n, &values(json!({ "email": "a@b.c" })), &visible)
+            .is_empty());
+        assert!(rules.check(&i18n, &Map::new(), &[]).is_empty());
+    }
+
+    #[test]
+    fn sum_against_limit() {
+        let i18n = I18n::new("en");
+        let rules = rules(json!([
+            { "type": "Sum", "keys": ["a", "b"], "operator": "lte", "other": "total" }
+        ]));
+        let visible = keys(&["a", "b", "total"]);
+
+        let errors = rules.check(
+            &i18n,
+            &values(json!({ "a": 4, "b": 7, "total": 10 })),
+            &visible,
+        );
+        assert_eq!(errors.keys().collect::<Vec<_>>(), ["a"]);
+        assert!(rules
+            .check(
+                &i18n,
+                &values(json!({ "a": 4, "b": 6, "total": 10 })),
+                &visible
+            )
+            .is_empty());
+        assert!(rules
+            .check(&i18n, &values(json!({ "a": 4, "b": 7 })), &visible)
+            .is_empty());
+    }
+
+    #[test]
+    fn first_error_per_field_wins() {
+        let i18n = I18n::new("en");
+        let rules = rules(json!([
+            { "type": "Compare", "key": "a", "operator": "gt", "other": "b" },
+            { "type": "Compare", "key": "a", "operator": "lt", "other": "c" }
+        ]));
+        let errors = rules.check(
+            &i18n,
+            &values(json!({ "a": 5, "b": 6, "c": 4 })),
+            &keys(&["a", "b", "c"]),
+        );
+        let first = FormRules(rules.0[..1].to_vec()).check(
+            &i18n,
+            &values(json!({ "a": 5, "b": 6 })),
+            &keys(&["a", "b"]),
+        );
+        assert_eq!(errors, first);
+    }
+
+    #[test]
+    fn validate_rule_keys() {
+        let rules = rules(json!([
+            { "type": "Compare", "key": "items.amount", "operator": "lte", "other": "limit" }
+        ]));
+        assert!(rules.is_valid(&keys(&["items", "limit"])));
+        assert!(!rules.is_valid(&keys(&["items"])));
+        assert!(!FormRules(vec![FormRule::AtLeastOne { keys: vec![] }]).is_valid(&[]));
+    }
+}
@@ -0,0 +1,152 @@
+use std::str::FromStr;
+
+use regex::Regex;
+use rocket::http::uri::Absolute;
+
+use crate::utils::string::StringExt;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct FormTextPattern {
+    pub regex: String,
+    pub message_zh: String,
+    pub message_en: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FormTextError {
+    TooShort,
+    TooLong,
+    TooFewWords,
+    TooManyWords,
+    InvalidEmail,
+    InvalidUrl,
+    InvalidPhone,
+    PatternMismatch,
+}
+
+impl FormTextError {
+    pub fn i18n_key(&self) -> &'static str {
+        match self {
+            FormTextError::TooShort => "ticket.rules.text_too_short",
+            FormTextError::TooLong => "ticket.rules.text_too_long",
+            FormTextError::TooFewWords => "ticket.rules.text_too_few_words",
+            FormTextError::TooManyWords => "ticket.rules.text_too_many_words",
+            FormTextError::InvalidEmail => "ticket.rules.invalid_email",
+            FormTextError::InvalidUrl => "ticket.rules.invalid_url",
+            FormTextError::InvalidPhone => "ticket.rules.invalid_phone",
+            FormTextError::PatternMismatch => "ticket.rules.pattern_mismatch",
+        }
+    }
+}
+
+impl FormTextPattern {
+    pub fn compile(&self) -> Option<Regex> {
+        Regex::new(&format!("^(?:{})$", self.regex)).ok()
+    }
+
+    pub fn check(&self, text: &str) -> Result<(), FormTextError> {
+        match self.compile() {
+            Some(regex) if regex.is_match(text) => Ok(()),
+            _ => Err(FormTextError::PatternMismatch),
+        }
+    }
+
+    pub fn message(&self, locale: &str) -> Option<String> {
+        let message = if locale == "zh" {
+            &self.message_zh
+        } else {
+            &self.message_en
+        };
+        if message.trim().is_empty() {
+            None
+        } else {
+            Some(message.clone())
+        }
+    }
+}
+
+pub fn is_valid_word_range(min_words: Option<u32>, max_words: Option<u32>) -> bool {
+    match (min_words, max_words) {
+        (Some(min_words), Some(max_words)) => min_words <= max_words,
+        _ => true,
+    }
+}
+
+pub fn check_length(
+    text: &str,
+    min_texts: u32,
+    max_texts: u32,
+    min_words: Option<u32>,
+    max_words: Option<u32>,
+) -> Result<(), FormTextError> {
+    let text_len = text.count_graphemes();
+    if text_len < min_texts {
+        return Err(FormTextError::TooShort);
+    }
+    if text_len > max_texts {
+        return Err(FormTextError::TooLong);
+    }
+    if min_words.is_some() || max_words.is_some() {
+        let words = text.count_unicode_words();
+        if min_words.is_some_and(|min_words| words < min_words) {
+            return Err(FormTextError::TooFewWords);
+        }
+        if max_words.is_some_and(|max_words| words > max_words) {
+            return Err(FormTextError::TooManyWords);
+        }
+    }
+    Ok(())
+}
+
+pub fn normalize_email(text: &str) -> Result<String, FormTextError> {
+    let address = lettre::Address::from_str(text).map_err(|_| FormTextError::InvalidEmail)?;
+    let domain = address.domain();
+    if !domain.contains('.') || domain.starts_with('.') || domain.ends_with('.') {
+        return Err(FormTextError::InvalidEmail);
+    }
+    Ok(address.to_string())
+}
+
+pub fn normalize_url(text: &str) -> Result<String, FormTextError> {
+    let uri = Absolute::parse(text).map_err(|_| FormTextError::InvalidUrl)?;
+    if !uri.scheme().eq_ignore_ascii_case("http") && !uri.scheme().eq_ignore_ascii_case("https") {
+        return Err(FormTextError::InvalidUrl);
+    }
+    match uri.authority() {
+        Some(authority) if !authority.host().is_empty() => Ok(text.to_owned()),
+        _ => Err(FormTextError::InvalidUrl),
+    }
+}
+
+pub fn normalize_phone(text: &str, calling_code: Option<u16>) -> Result<String, FormTextError> {
+    let (international, rest) = if let Some(rest) = text.strip_prefix('+') {
+        (true, rest)
+    } else if let Some(rest) = text.strip_prefix("00") {
+        (true, rest)
+    } else {
+        (false, text)
+    };
+
+    let mut digits = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            ' ' | '-' | '.' | '(' | ')' => (),
+            _ => return Err(FormTextError::InvalidPhone),
+        }
+    }
+
+    if !international {
+        let calling_code = calling_code.ok_or(FormTextError::InvalidPhone)?;
+        digits = format!(
+            "{}{}",
+            calling_code,
+            digits.strip_prefix('0').unwrap_or(&digits)
+        );
+    }
+
+    if digits.starts_with('0') || digits.len() < 7 || digits.len() > 15 {
+        return Err(FormTextError::InvalidPhone);
+    }
+    Ok(format!("+{}", digits))
+}
@@ -0,0 +1,101 @@
+use diesel::{
+    deserialize::FromSql,
+    mysql::{Mysql, MysqlValue},
+    serialize::ToSql,
+    sql_types,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::models::user::User;
+
+use self::models::TicketHistory;
+
+pub mod models;
+
+#[derive(FromSqlRow, AsExpression, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::VarChar)]
+pub enum TicketHistoryAction {
+    Process,
+    Claim,
+    Release,
+    Reassign,
+    Escalate,
+    Edit,
+    ForceComplete,
+    Skip,
+    Spawn,
+}
+
+impl TicketHistoryAction {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TicketHistoryAction::Process => "process",
+            TicketHistoryAction::Claim => "claim",
+            TicketHistoryAction::Release => "release",
+            TicketHistoryAction::Reassign => "reassign",
+            TicketHistoryAction::Escalate => "escalate",
+            TicketHistoryAction::Edit => "edit",
+            TicketHistoryAction::ForceComplete => "force_complete",
+            TicketHistoryAction::Skip => "skip",
+            TicketHistoryAction::Spawn => "spawn",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<TicketHistoryAction> {
+        match s {
+            "process" => Some(TicketHistoryAction::Process),
+            "claim" => Some(TicketHistoryAction::Claim),
+            "release" => Some(TicketHistoryAction::Release),
+            "reassign" => Some(TicketHistoryAction::Reassign),
+            "escalate" => Some(TicketHistoryAction::Escalate),
+            "edit" => Some(TicketHistoryAction::Edit),
+            "force_complete" => Some(TicketHistoryAction::ForceComplete),
+            "skip" => Some(TicketHistoryAction::Skip),
+            "spawn" => Some(TicketHistoryAction::Spawn),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for TicketHistoryAction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TicketHistoryAction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        TicketHistoryAction::from_str(&s)
+            .ok_or_else(|| serde::de::Error::custom("Invalid history action"))
+    }
+}
+
+impl FromSql<sql_types::VarChar, Mysql> for TicketHistoryAction {
+    fn from_sql(value: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let s = <String as FromSql<sql_types::VarChar, Mysql>>::from_sql(value)?;
+        TicketHistoryAction::from_str(&s).ok_or_else(|| "Invalid history action".into())
+    }
+}
+
+impl ToSql<sql_types::VarChar, Mysql> for TicketHistoryAction {
+    fn to_sql<'a>(
+        &'a self,
+        out: &mut diesel::serialize::Output<'a, '_, Mysql>,
+    ) -> diesel::serialize::Result {
+        ToSql::<sql_types::VarChar, Mysql>::to_sql(self.as_str(), out)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketHistoryItem {
+    #[serde(flatten)]
+    pub history: TicketHistory,
+    pub user: Option<User>,
+}
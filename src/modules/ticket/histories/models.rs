@@ -0,0 +1,80 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rocket::serde::json::serde_json;
+use rocket_db_pools::diesel::prelude::RunQueryDsl;
+
+use crate::models::user::User;
+use crate::modules::ticket::models::{Ticket, TicketFlow};
+use crate::schema::{ticket_histories, users};
+use crate::utils::serde::unix_time;
+
+use super::{TicketHistoryAction, TicketHistoryItem};
+
+#[derive(
+    Queryable,
+    Identifiable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Insertable,
+    AsChangeset,
+)]
+#[diesel(belongs_to(Ticket))]
+#[diesel(belongs_to(TicketFlow))]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = ticket_histories)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct TicketHistory {
+    pub id: i32,
+    pub ticket_id: i32,
+    pub ticket_flow_id: Option<i32>,
+    pub user_id: Option<String>,
+    pub action: TicketHistoryAction,
+    pub detail: Option<serde_json::Value>,
+    #[serde(with = "unix_time")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "unix_time")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl TicketHistory {
+    pub async fn create(
+        conn: &mut crate::DbConn,
+        ticket_id: i32,
+        ticket_flow: Option<&TicketFlow>,
+        user: Option<&User>,
+        action: TicketHistoryAction,
+        detail: Option<serde_json::Value>,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::insert_into(ticket_histories::table)
+            .values((
+                ticket_histories::ticket_id.eq(ticket_id),
+                ticket_histories::ticket_flow_id.eq(ticket_flow.map(|flow| flow.id)),
+                ticket_histories::user_id.eq(user.map(|user| user.id.clone())),
+                ticket_histories::action.eq(action),
+                ticket_histories::detail.eq(detail),
+            ))
+            .execute(conn)
+            .await
+    }
+
+    pub async fn get_ticket_histories(
+        conn: &mut crate::DbConn,
+        ticket: &Ticket,
+    ) -> Result<Vec<TicketHistoryItem>, diesel::result::Error> {
+        let histories: Vec<(TicketHistory, Option<User>)> = TicketHistory::belonging_to(ticket)
+            .left_join(users::table)
+            .order(ticket_histories::id.asc())
+            .select((TicketHistory::as_select(), Option::<User>::as_select()))
+            .load(conn)
+            .await?;
+
+        Ok(histories
+            .into_iter()
+            .map(|(history, user)| TicketHistoryItem { history, user })
+            .collect())
+    }
+}
@@ -1,18 +1,30 @@
+use chrono::NaiveDateTime;
+use rocket::serde::json::serde_json;
+
 use crate::models::role::Role;
 use crate::models::user::User;
+use crate::utils::serde::unix_time_option;
 use crate::DbConn;
 
+use self::forms::fields::FormFieldDefault;
 use self::forms::models::TicketFormAnswer;
 use self::forms::FormSchema;
 use self::models::{Ticket, TicketFlow, TicketSchema, TicketSchemaFlow};
-use self::reviews::models::{TicketReview, TicketSchemaReview};
+use self::reviews::models::TicketSchemaReview;
+use self::reviews::TicketFlowReview;
 
 use super::EnabledFeature;
 
 pub mod api;
+pub mod assignments;
+pub mod conditions;
+pub mod conflicts;
+pub mod escalations;
 pub mod forms;
+pub mod histories;
 pub mod models;
 pub mod reviews;
+pub mod spawns;
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "type")]
@@ -33,7 +45,7 @@ pub struct TicketSchemaFlowItem {
 pub enum TicketFlowValue {
     None,
     Form(TicketFormAnswer),
-    Review(TicketReview),
+    Review(TicketFlowReview),
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -41,9 +53,20 @@ pub enum TicketFlowValue {
 pub enum TicketFlowOperator {
     User(User),
     Role(Role),
+    System,
+    Hidden,
     None,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum TicketFlowAccess {
+    Direct,
+    OnBehalfOf(String),
+    Open,
+    Denied,
+    Conflicted,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TicketFlowItem {
     #[serde(flatten)]
@@ -52,6 +75,85 @@ pub struct TicketFlowItem {
     operator: TicketFlowOperator,
 }
 
+impl TicketSchemaFlowItem {
+    pub fn get_identifying_keys(&self) -> Vec<String> {
+        match &self.module {
+            TicketSchemaFlowValue::Form(form) => form
+                .fields
+                .iter()
+                .filter(|field| field.identifying)
+                .map(|field| field.key.clone())
+                .collect(),
+            TicketSchemaFlowValue::Review(_) => vec![],
+        }
+    }
+
+    pub fn redact(&mut self, identifying_keys: &[String]) {
+        if let TicketSchemaFlowValue::Form(form) = &mut self.module {
+            for field in form.fields.iter_mut() {
+                if let Some(Some(FormFieldDefault::Dynamic {
+                    field_key, value, ..
+                })) = field.define.default_mut()
+                {
+                    if identifying_keys.contains(field_key) {
+                        *value = None;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn localize(&mut self, locale: &str) {
+        if let TicketSchemaFlowValue::Form(form) = &mut self.module {
+            for field in form.fields.iter_mut() {
+                field.define.localize(locale);
+            }
+        }
+    }
+
+    pub async fn fill_remaining(
+        &mut self,
+        conn: &mut DbConn,
+        exclude_ticket_id: Option<i32>,
+    ) -> Result<(), diesel::result::Error> {
+        if let TicketSchemaFlowValue::Form(form) = &mut self.module {
+            if form.has_capacity() {
+                let answers =
+                    TicketFormAnswer::get_finished_values(conn, form.form.id, exclude_ticket_id)
+                        .await?;
+                form.fill_remaining(&answers);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TicketFlowItem {
+    pub fn redact(&mut self, user: &User, identifying_keys: &[String]) {
+        if let TicketFlowOperator::User(operator) = &self.operator {
+            if operator.id != user.id {
+                self.operator = TicketFlowOperator::Hidden;
+                self.flow.user_id = None;
+            }
+        }
+        match &mut self.module {
+            TicketFlowValue::Form(answer) => {
+                if let serde_json::Value::Object(value) = &mut answer.value {
+                    value.retain(|key, _| !identifying_keys.contains(key));
+                }
+            }
+            TicketFlowValue::Review(review) => {
+                for vote in std::iter::once(&mut review.review).chain(review.votes.iter_mut()) {
+                    if vote.user_id.as_ref() != Some(&user.id) {
+                        vote.user_id = None;
+                    }
+                }
+            }
+            TicketFlowValue::None => (),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TicketFlowStatus {
     schema: TicketSchemaFlowItem,
@@ -70,6 +172,9 @@ pub struct TicketWithStatus {
     #[serde(flatten)]
     pub ticket: Ticket,
     pub status: TicketStatus,
+    #[serde(with = "unix_time_option")]
+    pub due_at: Option<NaiveDateTime>,
+    pub overdue: bool,
 }
 
 pub async fn get_enabled_features_by_user(conn: &mut DbConn, user: &User) -> Vec<EnabledFeature> {
@@ -1,26 +1,38 @@
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap};
 
 use chrono::NaiveDateTime;
 use diesel::dsl::{max, min, Eq, Filter, GroupBy, IntoBoxed, Select};
 use diesel::mysql::Mysql;
 use diesel::prelude::*;
+use rocket::serde::json::serde_json;
 use rocket_db_pools::diesel::prelude::RunQueryDsl;
 
 use crate::models::role::Role;
 use crate::models::user::User;
+use crate::models::user_delegation::UserDelegation;
 use crate::models::{project::Project, target::Target};
 use crate::schema::{
-    labels, roles, targets, ticket_flows, ticket_form_answers, ticket_reviews, ticket_schema_flows,
-    ticket_schema_forms, ticket_schema_managers, ticket_schema_reviews, ticket_schemas, tickets,
-    users,
+    labels, roles, targets, ticket_conflicts, ticket_flow_assignees, ticket_flows,
+    ticket_form_answers, ticket_reviews, ticket_schema_flows, ticket_schema_forms,
+    ticket_schema_managers, ticket_schema_reviews, ticket_schemas, tickets, users,
 };
-use crate::utils::serde::unix_time;
+use crate::utils::serde::{unix_time, unix_time_option};
 
+use super::assignments::models::TicketFlowAssignee;
+use super::assignments::FlowAssignmentDefine;
+use super::conditions::FlowCondition;
+use super::conflicts::models::TicketConflict;
+use super::forms::dates::TicketSchemaDates;
 use super::forms::models::{TicketFormAnswer, TicketSchemaForm, TicketSchemaFormField};
 use super::forms::FormSchema;
+use super::histories::models::TicketHistory;
+use super::histories::TicketHistoryAction;
 use super::reviews::models::{TicketReview, TicketSchemaReview};
+use super::reviews::TicketFlowReview;
+use super::spawns::models::TicketSchemaSpawn;
+use super::spawns::TicketPrefill;
 use super::{
-    TicketFlowItem, TicketFlowOperator, TicketFlowValue, TicketSchemaFlowItem,
+    TicketFlowAccess, TicketFlowItem, TicketFlowOperator, TicketFlowValue, TicketSchemaFlowItem,
     TicketSchemaFlowValue, TicketStatus, TicketWithStatus,
 };
 
@@ -50,6 +62,7 @@ pub struct TicketSchema {
     pub updated_at: NaiveDateTime,
     pub title_en: String,
     pub description_en: String,
+    pub dates: Option<TicketSchemaDates>,
 }
 
 impl TicketSchema {
@@ -60,6 +73,7 @@ impl TicketSchema {
         description_zh: String,
         description_en: String,
         project_id: String,
+        dates: Option<TicketSchemaDates>,
     ) -> Result<TicketSchema, diesel::result::Error> {
         let _ = diesel::insert_into(ticket_schemas::table)
             .values((
@@ -68,6 +82,7 @@ impl TicketSchema {
                 ticket_schemas::title_en.eq(title_en),
                 ticket_schemas::description_en.eq(description_en),
                 ticket_schemas::project_id.eq(project_id),
+                ticket_schemas::dates.eq(dates),
             ))
             .execute(conn)
             .await;
@@ -380,6 +395,9 @@ impl TicketSchema {
         conn: &mut crate::DbConn,
         name_zh: String,
         name_en: String,
+        assignment: Option<FlowAssignmentDefine>,
+        due_hours: Option<i32>,
+        condition: Option<FlowCondition>,
     ) -> Result<TicketSchemaFlow, diesel::result::Error> {
         let max_order: Option<i32> = ticket_schema_flows::table
             .filter(ticket_schema_flows::ticket_schema_id.eq(self.id))
@@ -398,6 +416,9 @@ impl TicketSchema {
                 ticket_schema_flows::order.eq(order),
                 ticket_schema_flows::name_zh.eq(name_zh),
                 ticket_schema_flows::name_en.eq(name_en),
+                ticket_schema_flows::assignment.eq(assignment),
+                ticket_schema_flows::due_hours.eq(due_hours),
+                ticket_schema_flows::condition.eq(condition),
             ))
             .execute(conn)
             .await;
@@ -468,6 +489,9 @@ pub struct TicketSchemaFlow {
     #[serde(with = "unix_time")]
     pub updated_at: NaiveDateTime,
     pub name_en: String,
+    pub assignment: Option<FlowAssignmentDefine>,
+    pub due_hours: Option<i32>,
+    pub condition: Option<FlowCondition>,
 }
 
 impl TicketSchemaFlow {
@@ -532,10 +556,29 @@ impl TicketSchemaFlow {
     pub async fn get_probably_assign_users(
         &self,
         conn: &mut crate::DbConn,
+        ticket: Option<&Ticket>,
     ) -> Result<Vec<User>, diesel::result::Error> {
         let target = Target::find(conn, self.operator_id).await?;
+        let users = Target::get_users(conn, &vec![target]).await?;
 
-        Target::get_users(conn, &vec![target]).await
+        let ticket = match ticket {
+            Some(ticket) => ticket,
+            None => return Ok(users),
+        };
+
+        let reviews: i64 = TicketSchemaReview::belonging_to(self)
+            .count()
+            .get_result(conn)
+            .await?;
+        if reviews == 0 {
+            return Ok(users);
+        }
+
+        let conflicted_user_ids = TicketConflict::get_conflicted_user_ids(conn, ticket).await?;
+        Ok(users
+            .into_iter()
+            .filter(|user| !conflicted_user_ids.contains(&user.id))
+            .collect())
     }
 }
 
@@ -563,9 +606,13 @@ pub struct Ticket {
     pub created_at: NaiveDateTime,
     #[serde(with = "unix_time")]
     pub updated_at: NaiveDateTime,
+    pub parent_id: Option<i32>,
+    pub prefill: Option<TicketPrefill>,
 }
 
 impl Ticket {
+    pub const TITLE_MAX_LENGTH: usize = 150;
+
     pub async fn create(
         conn: &mut crate::DbConn,
         schema: &TicketSchema,
@@ -623,6 +670,55 @@ impl Ticket {
             .await
     }
 
+    pub async fn set_parent(
+        &mut self,
+        conn: &mut crate::DbConn,
+        parent: &Ticket,
+        prefill: TicketPrefill,
+    ) -> Result<usize, diesel::result::Error> {
+        self.parent_id = Some(parent.id);
+        self.prefill = Some(prefill);
+        diesel::update(tickets::table.filter(tickets::id.eq(self.id)))
+            .set((
+                tickets::parent_id.eq(self.parent_id),
+                tickets::prefill.eq(&self.prefill),
+            ))
+            .execute(conn)
+            .await
+    }
+
+    pub async fn get_parent(
+        &self,
+        conn: &mut crate::DbConn,
+    ) -> Result<Option<Ticket>, diesel::result::Error> {
+        match self.parent_id {
+            Some(parent_id) => Ticket::find(conn, parent_id).await.optional(),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_prefill(conn: &mut crate::DbConn, id: &i32) -> Option<TicketPrefill> {
+        tickets::table
+            .find(id)
+            .select(tickets::prefill)
+            .first::<Option<TicketPrefill>>(conn)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    pub async fn get_children(
+        &self,
+        conn: &mut crate::DbConn,
+    ) -> Result<Vec<Ticket>, diesel::result::Error> {
+        tickets::table
+            .filter(tickets::parent_id.eq(self.id))
+            .order(tickets::id.asc())
+            .select(Ticket::as_select())
+            .load(conn)
+            .await
+    }
+
     pub async fn get_pending_ticket_ids_by_user(
         conn: &mut crate::DbConn,
         user: &User,
@@ -634,29 +730,110 @@ impl Ticket {
             .load::<Option<i32>>(conn)
             .await?;
 
-        let mut pending_user_ticket_ids: Vec<_> = ticket_flows::table
+        let mut user_ids = UserDelegation::get_active_delegator_ids(conn, user).await?;
+        user_ids.push(user.id.clone());
+
+        let pending_user_ticket_flows: Vec<(i32, Option<i32>)> = ticket_flows::table
+            .inner_join(ticket_schema_flows::table.left_join(ticket_schema_reviews::table))
             .filter(ticket_flows::id.nullable().eq_any(&latest_ticket_flow_ids))
-            .filter(ticket_flows::user_id.eq(user.id.clone()))
-            .select(ticket_flows::ticket_id)
+            .filter(ticket_flows::user_id.eq_any(&user_ids))
+            .select((
+                ticket_flows::ticket_id,
+                ticket_schema_reviews::id.nullable(),
+            ))
             .distinct()
-            .load::<i32>(conn)
+            .load(conn)
+            .await?;
+
+        let voted_ticket_flow_ids: Vec<i32> = ticket_reviews::table
+            .filter(
+                ticket_reviews::ticket_flow_id
+                    .nullable()
+                    .eq_any(&latest_ticket_flow_ids),
+            )
+            .filter(ticket_reviews::user_id.eq_any(&user_ids))
+            .select(ticket_reviews::ticket_flow_id)
+            .load(conn)
             .await?;
 
-        let mut pending_ticket_ids: Vec<_> = ticket_flows::table
-            .inner_join(ticket_schema_flows::table.inner_join(targets::table))
+        let assigned_ticket_flows: Vec<(i32, String)> = ticket_flow_assignees::table
+            .filter(
+                ticket_flow_assignees::ticket_flow_id
+                    .nullable()
+                    .eq_any(&latest_ticket_flow_ids),
+            )
+            .select((
+                ticket_flow_assignees::ticket_flow_id,
+                ticket_flow_assignees::user_id,
+            ))
+            .load(conn)
+            .await?;
+
+        let assigned_ticket_flow_ids = assigned_ticket_flows
+            .iter()
+            .map(|(ticket_flow_id, _)| *ticket_flow_id)
+            .collect::<Vec<_>>();
+        let user_assigned_ticket_flow_ids = assigned_ticket_flows
+            .iter()
+            .filter(|(_, user_id)| user_ids.contains(user_id))
+            .map(|(ticket_flow_id, _)| *ticket_flow_id)
+            .collect::<Vec<_>>();
+
+        let pending_assigned_ticket_flows: Vec<(i32, Option<i32>)> = ticket_flows::table
+            .inner_join(ticket_schema_flows::table.left_join(ticket_schema_reviews::table))
+            .filter(ticket_flows::id.eq_any(&user_assigned_ticket_flow_ids))
+            .filter(ticket_flows::id.ne_all(&voted_ticket_flow_ids))
+            .filter(ticket_flows::user_id.is_null())
+            .select((
+                ticket_flows::ticket_id,
+                ticket_schema_reviews::id.nullable(),
+            ))
+            .distinct()
+            .load(conn)
+            .await?;
+
+        let pending_ticket_flows: Vec<(i32, Option<i32>)> = ticket_flows::table
+            .inner_join(
+                ticket_schema_flows::table
+                    .inner_join(targets::table)
+                    .left_join(ticket_schema_reviews::table),
+            )
             .filter(ticket_flows::id.nullable().eq_any(&latest_ticket_flow_ids))
+            .filter(ticket_flows::id.ne_all(&voted_ticket_flow_ids))
+            .filter(ticket_flows::id.ne_all(&assigned_ticket_flow_ids))
             .filter(ticket_flows::user_id.is_null())
             .filter(
                 targets::user_id
                     .eq(user.id.clone())
                     .or(targets::label_id.eq_any(user.build_user_labels_query().nullable())),
             )
-            .select(ticket_flows::ticket_id)
+            .select((
+                ticket_flows::ticket_id,
+                ticket_schema_reviews::id.nullable(),
+            ))
             .distinct()
             .load(conn)
             .await?;
 
-        pending_user_ticket_ids.append(&mut pending_ticket_ids);
+        let pending_user_ticket_flows = pending_user_ticket_flows
+            .into_iter()
+            .chain(pending_assigned_ticket_flows)
+            .chain(pending_ticket_flows)
+            .collect::<Vec<_>>();
+        let review_ticket_ids = pending_user_ticket_flows
+            .iter()
+            .filter(|(_, review_id)| review_id.is_some())
+            .map(|(ticket_id, _)| *ticket_id)
+            .collect::<Vec<_>>();
+        let conflicted_ticket_ids =
+            TicketConflict::get_conflicted_ticket_ids(conn, user, &review_ticket_ids).await?;
+        let pending_user_ticket_ids = pending_user_ticket_flows
+            .into_iter()
+            .filter(|(ticket_id, review_id)| {
+                review_id.is_none() || !conflicted_ticket_ids.contains(ticket_id)
+            })
+            .map(|(ticket_id, _)| ticket_id)
+            .collect::<Vec<_>>();
 
         Ok(pending_user_ticket_ids)
     }
@@ -702,20 +879,18 @@ impl Ticket {
             .load(conn)
             .await?;
 
-        let mut tickets = pending_tickets
+        let tickets = pending_tickets
             .into_iter()
-            .map(|ticket| TicketWithStatus {
-                ticket,
-                status: TicketStatus::Pending,
-            })
+            .map(|ticket| (ticket, TicketStatus::Pending))
             .chain(other_tickets.into_iter().map(|ticket| {
                 let status = match ticket.finished {
                     true => TicketStatus::Finished,
                     false => TicketStatus::InProgress,
                 };
-                TicketWithStatus { ticket, status }
+                (ticket, status)
             }))
             .collect::<Vec<_>>();
+        let mut tickets = Self::with_status(conn, tickets).await?;
 
         let get_ticket_order = |s: &TicketStatus| match s {
             TicketStatus::Pending => 0,
@@ -742,17 +917,234 @@ impl Ticket {
         conn: &mut crate::DbConn,
         user: &User,
     ) -> Result<bool, diesel::result::Error> {
-        let flows: Vec<TicketFlow> = TicketFlow::belonging_to(self)
-            .filter(ticket_flows::user_id.eq(user.id.clone()))
-            .load(conn)
-            .await?;
+        let mut user_ids = UserDelegation::get_active_delegator_ids(conn, user).await?;
+        user_ids.push(user.id.clone());
+
+        let flows: Vec<TicketFlow> = TicketFlow::belonging_to(self).load(conn).await?;
 
-        if !flows.is_empty() {
+        if flows.iter().any(|flow| {
+            flow.user_id
+                .as_ref()
+                .is_some_and(|user_id| user_ids.contains(user_id))
+        }) {
             return Ok(true);
         }
 
         let schema = TicketSchema::find(conn, self.ticket_schema_id).await?;
-        schema.is_probably_join_user(conn, user).await
+        if schema.is_probably_join_user(conn, user).await? {
+            return Ok(true);
+        }
+
+        let votes: i64 = ticket_reviews::table
+            .filter(
+                ticket_reviews::ticket_flow_id
+                    .eq_any(flows.iter().map(|flow| flow.id).collect::<Vec<_>>()),
+            )
+            .filter(ticket_reviews::user_id.eq_any(&user_ids))
+            .count()
+            .get_result(conn)
+            .await?;
+        if votes > 0 {
+            return Ok(true);
+        }
+
+        match self.get_process_flow(conn).await.optional()? {
+            Some(flow) => Ok(matches!(
+                flow.get_eligible_access(conn, user).await?,
+                TicketFlowAccess::Direct | TicketFlowAccess::OnBehalfOf(_) | TicketFlowAccess::Open
+            )),
+            None => Ok(false),
+        }
+    }
+
+    pub async fn is_blind_user(
+        &self,
+        conn: &mut crate::DbConn,
+        user: &User,
+    ) -> Result<bool, diesel::result::Error> {
+        let blind_ticket_ids = Ticket::get_blind_ticket_ids(conn, user, &[self]).await?;
+        Ok(blind_ticket_ids.contains(&self.id))
+    }
+
+    pub async fn get_blind_ticket_ids(
+        conn: &mut crate::DbConn,
+        user: &User,
+        tickets: &[&Ticket],
+    ) -> Result<Vec<i32>, diesel::result::Error> {
+        if tickets.is_empty() {
+            return Ok(vec![]);
+        }
+        let ticket_ids = tickets.iter().map(|ticket| ticket.id).collect::<Vec<_>>();
+        let schema_ids = tickets
+            .iter()
+            .map(|ticket| ticket.ticket_schema_id)
+            .collect::<Vec<_>>();
+
+        let user_label_ids: Vec<i32> = user.build_user_labels_query().load(conn).await?;
+        let is_user_target = |target: &Target| {
+            target
+                .label_id
+                .is_some_and(|label_id| user_label_ids.contains(&label_id))
+                || target.user_id.as_ref() == Some(&user.id)
+        };
+
+        let managed_schema_ids: Vec<i32> = ticket_schema_managers::table
+            .inner_join(targets::table)
+            .filter(ticket_schema_managers::ticket_schema_id.eq_any(&schema_ids))
+            .select((
+                ticket_schema_managers::ticket_schema_id,
+                Target::as_select(),
+            ))
+            .load::<(i32, Target)>(conn)
+            .await?
+            .into_iter()
+            .filter(|(_, target)| is_user_target(target))
+            .map(|(schema_id, _)| schema_id)
+            .collect();
+
+        let flows: Vec<(TicketFlow, Target, Option<TicketSchemaReview>)> = ticket_flows::table
+            .filter(ticket_flows::ticket_id.eq_any(&ticket_ids))
+            .inner_join(
+                ticket_schema_flows::table
+                    .inner_join(targets::table)
+                    .left_join(ticket_schema_reviews::table),
+            )
+            .select((
+                TicketFlow::as_select(),
+                Target::as_select(),
+                Option::<TicketSchemaReview>::as_select(),
+            ))
+            .load(conn)
+            .await?;
+        let flow_ids = flows.iter().map(|(flow, ..)| flow.id).collect::<Vec<_>>();
+
+        let delegator_ids = UserDelegation::get_active_delegator_ids(conn, user).await?;
+        let mut user_ids = delegator_ids.clone();
+        user_ids.push(user.id.clone());
+
+        let voted_flow_ids: Vec<i32> = ticket_reviews::table
+            .filter(ticket_reviews::ticket_flow_id.eq_any(&flow_ids))
+            .filter(ticket_reviews::user_id.eq_any(&user_ids))
+            .select(ticket_reviews::ticket_flow_id)
+            .load(conn)
+            .await?;
+
+        let assignees: Vec<(i32, String)> = ticket_flow_assignees::table
+            .filter(ticket_flow_assignees::ticket_flow_id.eq_any(&flow_ids))
+            .order(ticket_flow_assignees::user_id.asc())
+            .select((
+                ticket_flow_assignees::ticket_flow_id,
+                ticket_flow_assignees::user_id,
+            ))
+            .load(conn)
+            .await?;
+
+        let conflicts: Vec<TicketConflict> = ticket_conflicts::table
+            .filter(ticket_conflicts::ticket_schema_id.eq_any(&schema_ids))
+            .filter(ticket_conflicts::user_id.eq_any(&user_ids))
+            .filter(
+                ticket_conflicts::ticket_id
+                    .eq_any(&ticket_ids)
+                    .or(ticket_conflicts::ticket_id.is_null()),
+            )
+            .select(TicketConflict::as_select())
+            .load(conn)
+            .await?;
+        let answers: Vec<(i32, serde_json::Value)> = if conflicts.is_empty() {
+            vec![]
+        } else {
+            ticket_form_answers::table
+                .inner_join(ticket_flows::table)
+                .filter(ticket_flows::ticket_id.eq_any(&ticket_ids))
+                .select((ticket_flows::ticket_id, ticket_form_answers::value))
+                .load(conn)
+                .await?
+        };
+
+        let mut blind_ticket_ids = vec![];
+        for ticket in tickets {
+            if managed_schema_ids.contains(&ticket.ticket_schema_id) {
+                continue;
+            }
+            let ticket_answers = answers
+                .iter()
+                .filter(|(ticket_id, _)| *ticket_id == ticket.id)
+                .map(|(_, value)| value.clone())
+                .collect::<Vec<_>>();
+
+            let flow_reviews = flows
+                .iter()
+                .filter(|(flow, ..)| flow.ticket_id == ticket.id)
+                .filter(|(flow, operator, schema_review)| {
+                    if voted_flow_ids.contains(&flow.id) {
+                        return true;
+                    }
+                    let assignee_ids = match &flow.user_id {
+                        Some(user_id) => vec![user_id],
+                        None => assignees
+                            .iter()
+                            .filter(|(flow_id, _)| *flow_id == flow.id)
+                            .map(|(_, user_id)| user_id)
+                            .collect(),
+                    };
+                    let acting_user_id =
+                        if assignee_ids.is_empty() || assignee_ids.contains(&&user.id) {
+                            &user.id
+                        } else {
+                            match assignee_ids
+                                .into_iter()
+                                .find(|assignee_id| delegator_ids.contains(assignee_id))
+                            {
+                                Some(assignee_id) => assignee_id,
+                                None => return false,
+                            }
+                        };
+                    if acting_user_id == &user.id && !is_user_target(operator) {
+                        return false;
+                    }
+                    schema_review.is_none()
+                        || !conflicts.iter().any(|conflict| {
+                            &conflict.user_id == acting_user_id
+                                && conflict.ticket_schema_id == ticket.ticket_schema_id
+                                && conflict.is_match(ticket.id, &ticket_answers)
+                        })
+                })
+                .map(|(_, _, schema_review)| schema_review)
+                .collect::<Vec<_>>();
+
+            let is_blind = !flow_reviews.is_empty()
+                && flow_reviews.iter().all(|schema_review| {
+                    matches!(
+                        schema_review,
+                        Some(TicketSchemaReview {
+                            anonymous: true,
+                            ..
+                        })
+                    )
+                });
+            if is_blind {
+                blind_ticket_ids.push(ticket.id);
+            }
+        }
+
+        Ok(blind_ticket_ids)
+    }
+
+    pub fn redact(&mut self) {
+        self.title = format!("#{}", self.id);
+        self.prefill = None;
+    }
+
+    pub async fn get_blind_schema_ids(
+        conn: &mut crate::DbConn,
+    ) -> Result<Vec<i32>, diesel::result::Error> {
+        ticket_schema_flows::table
+            .inner_join(ticket_schema_reviews::table)
+            .filter(ticket_schema_reviews::anonymous.eq(true))
+            .select(ticket_schema_flows::ticket_schema_id)
+            .distinct()
+            .load(conn)
+            .await
     }
 
     pub async fn get_schema(
@@ -804,69 +1196,120 @@ impl Ticket {
             Option<User>,
             Option<Role>,
             Option<TicketFormAnswer>,
-            Option<TicketReview>,
-        )> =
-            TicketFlow::belonging_to(self)
-                .inner_join(
-                    ticket_schema_flows::table.inner_join(
+            Option<TicketSchemaReview>,
+        )> = TicketFlow::belonging_to(self)
+            .inner_join(
+                ticket_schema_flows::table
+                    .inner_join(
                         targets::table.left_join(schema_users).left_join(
                             labels::table.inner_join(
                                 roles::table
                                     .on(labels::value.eq(roles::id).and(labels::key.eq("role"))),
                             ),
                         ),
-                    ),
-                )
-                .left_join(direct_users)
-                .left_join(ticket_form_answers::table)
-                .left_join(ticket_reviews::table)
-                .order(ticket_schema_flows::order.asc())
-                .select((
-                    TicketFlow::as_select(),
-                    direct_users.fields(users::all_columns.nullable()),
-                    schema_users.fields(users::all_columns.nullable()),
-                    roles::all_columns.nullable(),
-                    Option::<TicketFormAnswer>::as_select(),
-                    Option::<TicketReview>::as_select(),
-                ))
-                .load(conn)
-                .await?;
+                    )
+                    .left_join(ticket_schema_reviews::table),
+            )
+            .left_join(direct_users)
+            .left_join(ticket_form_answers::table)
+            .order(ticket_schema_flows::order.asc())
+            .select((
+                TicketFlow::as_select(),
+                direct_users.fields(users::all_columns.nullable()),
+                schema_users.fields(users::all_columns.nullable()),
+                roles::all_columns.nullable(),
+                Option::<TicketFormAnswer>::as_select(),
+                Option::<TicketSchemaReview>::as_select(),
+            ))
+            .load(conn)
+            .await?;
 
-        flows
-            .into_iter()
-            .map(
-                |(flow, flow_user, schema_user, schema_label, form, review)| {
-                    let operator = match (flow_user, schema_user, schema_label) {
-                        (Some(user), _, _) => TicketFlowOperator::User(user),
-                        (_, Some(user), _) => TicketFlowOperator::User(user),
-                        (_, _, Some(role)) => TicketFlowOperator::Role(role),
-                        (None, None, None) => TicketFlowOperator::None,
-                    };
+        let mut reviews: Vec<TicketReview> = ticket_reviews::table
+            .filter(
+                ticket_reviews::ticket_flow_id
+                    .eq_any(flows.iter().map(|(flow, ..)| flow.id).collect::<Vec<_>>()),
+            )
+            .order(ticket_reviews::updated_at.asc())
+            .select(TicketReview::as_select())
+            .load(conn)
+            .await?;
 
-                    if let Some(form) = form {
-                        return Ok(TicketFlowItem {
-                            flow,
-                            module: TicketFlowValue::Form(form),
-                            operator,
-                        });
-                    }
+        let vote_flows = flows
+            .iter()
+            .filter(|(.., schema_review)| {
+                schema_review
+                    .as_ref()
+                    .is_some_and(|review| review.vote.is_some())
+            })
+            .map(|(flow, ..)| flow)
+            .collect::<Vec<_>>();
+        let eligible_reviewer_ids =
+            TicketFlow::get_eligible_reviewer_ids(conn, self, &vote_flows).await?;
+
+        let mut result: Vec<TicketFlowItem> = vec![];
+
+        for (flow, flow_user, schema_user, schema_label, form, schema_review) in flows.into_iter() {
+            let operator = match (flow_user, schema_user, schema_label) {
+                (Some(user), _, _) => TicketFlowOperator::User(user),
+                (_, Some(user), _) => TicketFlowOperator::User(user),
+                (_, _, Some(role)) => TicketFlowOperator::Role(role),
+                (None, None, None) => TicketFlowOperator::None,
+            };
+
+            if let Some(form) = form {
+                result.push(TicketFlowItem {
+                    flow,
+                    module: TicketFlowValue::Form(form),
+                    operator,
+                });
+                continue;
+            }
+
+            let (votes, others): (Vec<_>, Vec<_>) = reviews
+                .into_iter()
+                .partition(|review| review.ticket_flow_id == flow.id);
+            reviews = others;
 
-                    if let Some(review) = review {
-                        return Ok(TicketFlowItem {
-                            flow,
-                            module: TicketFlowValue::Review(review),
-                            operator,
-                        });
+            if let Some(review) = votes.last().cloned() {
+                let operator = match review.user_id {
+                    Some(_) => operator,
+                    None => TicketFlowOperator::System,
+                };
+                let score = schema_review
+                    .as_ref()
+                    .and_then(|r| r.rubric.as_ref())
+                    .and_then(|rubric| rubric.average_score(&votes));
+                let tally = match schema_review.and_then(|r| r.vote) {
+                    Some(vote) => {
+                        let eligible = eligible_reviewer_ids
+                            .get(&flow.id)
+                            .map_or(0, |ids| ids.len());
+                        Some(vote.tally(&votes, eligible))
                     }
+                    None => None,
+                };
 
-                    Ok(TicketFlowItem {
-                        flow,
-                        module: TicketFlowValue::None,
-                        operator,
-                    })
-                },
-            )
-            .collect()
+                result.push(TicketFlowItem {
+                    flow,
+                    module: TicketFlowValue::Review(TicketFlowReview {
+                        review,
+                        votes,
+                        tally,
+                        score,
+                    }),
+                    operator,
+                });
+                continue;
+            }
+
+            result.push(TicketFlowItem {
+                flow,
+                module: TicketFlowValue::None,
+                operator,
+            });
+        }
+
+        Ok(result)
     }
 
     pub async fn get_process_flow(
@@ -877,21 +1320,191 @@ impl Ticket {
             .inner_join(ticket_schema_flows::table)
             .filter(ticket_flows::ticket_id.eq(self.id))
             .filter(ticket_flows::finished.eq(false))
+            .filter(ticket_flows::skipped.eq(false))
             .order(ticket_schema_flows::order.asc())
             .select(TicketFlow::as_select())
             .first(conn)
             .await
     }
 
+    pub async fn activate_process_flow(
+        &self,
+        conn: &mut crate::DbConn,
+    ) -> Result<(), diesel::result::Error> {
+        let mut auto_reviewed_flow_ids: Vec<i32> = vec![];
+        let (mut flow, schema_flow) = loop {
+            let mut flow = match self.get_process_flow(conn).await {
+                Ok(flow) => flow,
+                Err(diesel::result::Error::NotFound) => {
+                    let _ = self.set_finish(conn, true).await?;
+                    let _ = TicketSchemaSpawn::spawn_tickets(conn, self).await?;
+                    return Ok(());
+                }
+                Err(err) => return Err(err),
+            };
+            let schema_flow = TicketSchemaFlow::find(conn, flow.ticket_schema_flow_id).await?;
+
+            let is_satisfied = match &schema_flow.condition {
+                Some(condition) => condition.is_satisfied(conn, &self.id).await,
+                None => true,
+            };
+            if is_satisfied {
+                if auto_reviewed_flow_ids.contains(&flow.id) {
+                    break (flow, schema_flow);
+                }
+                let review_schema = TicketSchemaReview::belonging_to(&schema_flow)
+                    .select(TicketSchemaReview::as_select())
+                    .first(conn)
+                    .await
+                    .optional()?;
+                let decision = match review_schema.as_ref().and_then(|r| r.rules.as_ref()) {
+                    Some(rules) => rules
+                        .resolve(conn, &self.id)
+                        .await
+                        .decide()
+                        .map(|(index, rule)| (index, rule.clone())),
+                    None => None,
+                };
+                let (review_schema, (index, rule)) = match (review_schema, decision) {
+                    (Some(review_schema), Some(decision)) => (review_schema, decision),
+                    _ => break (flow, schema_flow),
+                };
+
+                auto_reviewed_flow_ids.push(flow.id);
+                let _ = TicketReview::save_or_create(
+                    conn,
+                    &flow,
+                    &review_schema,
+                    None,
+                    rule.approved,
+                    rule.comment.clone(),
+                    None,
+                )
+                .await?;
+                let _ = flow.bump_version(conn).await?;
+                let _ = TicketHistory::create(
+                    conn,
+                    self.id,
+                    Some(&flow),
+                    None,
+                    TicketHistoryAction::Process,
+                    Some(serde_json::json!({
+                        "rule": index,
+                        "approved": rule.approved,
+                    })),
+                )
+                .await?;
+                self.apply_review_decision(conn, &mut flow, &review_schema, rule.approved)
+                    .await?;
+                continue;
+            }
+
+            flow.finished = true;
+            flow.skipped = true;
+            let _ = flow.save(conn).await?;
+            let _ = TicketHistory::create(
+                conn,
+                self.id,
+                Some(&flow),
+                None,
+                TicketHistoryAction::Skip,
+                Some(serde_json::json!({ "condition": schema_flow.condition })),
+            )
+            .await?;
+        };
+
+        if flow.due_at.is_none() {
+            if let Some(due_hours) = schema_flow.due_hours {
+                flow.due_at = Some(
+                    chrono::Utc::now().naive_utc() + chrono::Duration::hours(due_hours as i64),
+                );
+                let _ = flow.save(conn).await?;
+            }
+        }
+
+        if let Some(user_id) = &flow.user_id {
+            let reviews: i64 = TicketSchemaReview::belonging_to(&schema_flow)
+                .count()
+                .get_result(conn)
+                .await?;
+            if reviews == 0 || !TicketConflict::is_conflicted(conn, self.id, user_id).await? {
+                return Ok(());
+            }
+            flow.user_id = None;
+            flow.claimed = false;
+            let _ = flow.save(conn).await?;
+        }
+
+        let assignees = TicketFlowAssignee::get_flow_assignees(conn, &flow).await?;
+        if !assignees.is_empty() {
+            return Ok(());
+        }
+
+        let _ = flow.assign(conn).await?;
+        Ok(())
+    }
+
+    pub async fn apply_review_decision(
+        &self,
+        conn: &mut crate::DbConn,
+        flow: &mut TicketFlow,
+        review_schema: &TicketSchemaReview,
+        approved: bool,
+    ) -> Result<(), diesel::result::Error> {
+        if approved {
+            flow.finished = true;
+            let _ = flow.save(conn).await?;
+
+            let latest_flow = self.get_latest_flow(conn).await?;
+            if latest_flow.id == flow.id {
+                let _ = self.set_finish(conn, true).await?;
+            }
+            return Ok(());
+        }
+
+        if review_schema.vote.is_some() {
+            let _ = TicketReview::delete_flow_reviews(conn, flow).await?;
+        }
+
+        flow.reset_due();
+        let _ = flow.save(conn).await?;
+        if review_schema.restarted {
+            let flows = self.get_flows(conn).await?;
+            for mut flow in flows.into_iter() {
+                flow.flow.finished = false;
+                flow.flow.skipped = false;
+                flow.flow.reset_due();
+                let _ = flow.flow.save(conn).await?;
+            }
+        } else {
+            let mut previous_flow = self.get_previous_flow(conn, flow).await?;
+            previous_flow.finished = false;
+            previous_flow.reset_due();
+            let _ = previous_flow.save(conn).await?;
+        }
+
+        let (_, schema_flows) = self.get_schema(conn).await?;
+        for schema_flow in schema_flows.into_iter() {
+            if let TicketSchemaFlowValue::Form(form_schema) = schema_flow.module {
+                form_schema.promote_waitlist(conn).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn get_previous_flow(
         &self,
         conn: &mut crate::DbConn,
         flow: &TicketFlow,
     ) -> Result<TicketFlow, diesel::result::Error> {
+        let schema_flow = TicketSchemaFlow::find(conn, flow.ticket_schema_flow_id).await?;
+
         ticket_flows::table
             .inner_join(ticket_schema_flows::table)
             .filter(ticket_flows::ticket_id.eq(self.id))
-            .filter(ticket_schema_flows::order.lt(flow.ticket_schema_flow_id))
+            .filter(ticket_flows::skipped.eq(false))
+            .filter(ticket_schema_flows::order.lt(schema_flow.order))
             .order(ticket_schema_flows::order.desc())
             .select(TicketFlow::as_select())
             .first(conn)
@@ -905,11 +1518,55 @@ impl Ticket {
         ticket_flows::table
             .inner_join(ticket_schema_flows::table)
             .filter(ticket_flows::ticket_id.eq(self.id))
+            .filter(ticket_flows::skipped.eq(false))
             .order(ticket_schema_flows::order.desc())
             .select(TicketFlow::as_select())
             .first(conn)
             .await
     }
+
+    pub async fn get_due_dates(
+        conn: &mut crate::DbConn,
+        ticket_ids: &Vec<i32>,
+    ) -> Result<HashMap<i32, NaiveDateTime>, diesel::result::Error> {
+        let due_dates: Vec<(i32, Option<NaiveDateTime>)> = ticket_flows::table
+            .filter(ticket_flows::ticket_id.eq_any(ticket_ids))
+            .filter(ticket_flows::finished.eq(false))
+            .filter(ticket_flows::due_at.is_not_null())
+            .select((ticket_flows::ticket_id, ticket_flows::due_at))
+            .load(conn)
+            .await?;
+
+        Ok(due_dates
+            .into_iter()
+            .filter_map(|(ticket_id, due_at)| due_at.map(|due_at| (ticket_id, due_at)))
+            .collect())
+    }
+
+    pub async fn with_status(
+        conn: &mut crate::DbConn,
+        tickets: Vec<(Ticket, TicketStatus)>,
+    ) -> Result<Vec<TicketWithStatus>, diesel::result::Error> {
+        let ticket_ids = tickets
+            .iter()
+            .map(|(ticket, _)| ticket.id)
+            .collect::<Vec<_>>();
+        let due_dates = Self::get_due_dates(conn, &ticket_ids).await?;
+        let now = chrono::Utc::now().naive_utc();
+
+        Ok(tickets
+            .into_iter()
+            .map(|(ticket, status)| {
+                let due_at = due_dates.get(&ticket.id).cloned();
+                TicketWithStatus {
+                    overdue: due_at.is_some_and(|due_at| due_at < now),
+                    due_at,
+                    ticket,
+                    status,
+                }
+            })
+            .collect())
+    }
 }
 
 #[derive(
@@ -939,6 +1596,13 @@ pub struct TicketFlow {
     pub created_at: NaiveDateTime,
     #[serde(with = "unix_time")]
     pub updated_at: NaiveDateTime,
+    #[serde(with = "unix_time_option")]
+    pub due_at: Option<NaiveDateTime>,
+    #[serde(with = "unix_time_option")]
+    pub escalated_at: Option<NaiveDateTime>,
+    pub version: i32,
+    pub skipped: bool,
+    pub claimed: bool,
 }
 
 impl TicketFlow {
@@ -969,6 +1633,13 @@ impl TicketFlow {
             .await?)
     }
 
+    pub async fn find(
+        conn: &mut crate::DbConn,
+        id: i32,
+    ) -> Result<TicketFlow, diesel::result::Error> {
+        ticket_flows::table.find(id).first(conn).await
+    }
+
     pub async fn get_schema(
         &self,
         conn: &mut crate::DbConn
@@ -978,6 +1649,236 @@ impl TicketFlow {
         schema_flow.get_detail(conn, &self.ticket_id).await
     }
 
+    pub async fn get_eligible_reviewer_ids(
+        conn: &mut crate::DbConn,
+        ticket: &Ticket,
+        flows: &[&TicketFlow],
+    ) -> Result<HashMap<i32, Vec<String>>, diesel::result::Error> {
+        let mut result = HashMap::new();
+        if flows.is_empty() {
+            return Ok(result);
+        }
+
+        let conflicted_user_ids = TicketConflict::get_conflicted_user_ids(conn, ticket).await?;
+        let assignees: Vec<(i32, String)> = ticket_flow_assignees::table
+            .filter(
+                ticket_flow_assignees::ticket_flow_id
+                    .eq_any(flows.iter().map(|flow| flow.id).collect::<Vec<_>>()),
+            )
+            .select((
+                ticket_flow_assignees::ticket_flow_id,
+                ticket_flow_assignees::user_id,
+            ))
+            .load(conn)
+            .await?;
+        let schema_flow_targets: Vec<(i32, Target)> = ticket_schema_flows::table
+            .inner_join(targets::table)
+            .filter(
+                ticket_schema_flows::id.eq_any(
+                    flows
+                        .iter()
+                        .map(|flow| flow.ticket_schema_flow_id)
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .select((ticket_schema_flows::id, Target::as_select()))
+            .load(conn)
+            .await?;
+        let mut targets: HashMap<i32, Vec<Target>> = HashMap::new();
+        for (schema_flow_id, target) in schema_flow_targets.into_iter() {
+            targets.entry(schema_flow_id).or_default().push(target);
+        }
+
+        let mut target_user_ids: HashMap<i32, Vec<String>> = HashMap::new();
+        for flow in flows.iter() {
+            let mut user_ids = match &flow.user_id {
+                Some(user_id) => vec![user_id.clone()],
+                None => assignees
+                    .iter()
+                    .filter(|(flow_id, _)| *flow_id == flow.id)
+                    .map(|(_, user_id)| user_id.clone())
+                    .collect(),
+            };
+            if user_ids.is_empty() {
+                if let Entry::Vacant(entry) = target_user_ids.entry(flow.ticket_schema_flow_id) {
+                    let flow_targets = targets
+                        .remove(&flow.ticket_schema_flow_id)
+                        .unwrap_or_default();
+                    let users = Target::get_users(conn, &flow_targets).await?;
+                    entry.insert(users.into_iter().map(|user| user.id).collect());
+                }
+                user_ids = target_user_ids[&flow.ticket_schema_flow_id].clone();
+            }
+            user_ids.retain(|user_id| !conflicted_user_ids.contains(user_id));
+            result.insert(flow.id, user_ids);
+        }
+
+        Ok(result)
+    }
+
+    pub async fn get_access(
+        &self,
+        conn: &mut crate::DbConn,
+        user: &User,
+    ) -> Result<TicketFlowAccess, diesel::result::Error> {
+        let assignee_ids = match &self.user_id {
+            Some(user_id) => vec![user_id.clone()],
+            None => TicketFlowAssignee::get_flow_assignees(conn, self)
+                .await?
+                .into_iter()
+                .map(|assignee| assignee.id)
+                .collect(),
+        };
+
+        if assignee_ids.is_empty() {
+            return Ok(TicketFlowAccess::Open);
+        }
+        if assignee_ids.contains(&user.id) {
+            return Ok(TicketFlowAccess::Direct);
+        }
+
+        let delegator_ids = UserDelegation::get_active_delegator_ids(conn, user).await?;
+        match assignee_ids
+            .into_iter()
+            .find(|assignee_id| delegator_ids.contains(assignee_id))
+        {
+            Some(assignee_id) => Ok(TicketFlowAccess::OnBehalfOf(assignee_id)),
+            None => Ok(TicketFlowAccess::Denied),
+        }
+    }
+
+    pub async fn get_eligible_access(
+        &self,
+        conn: &mut crate::DbConn,
+        user: &User,
+    ) -> Result<TicketFlowAccess, diesel::result::Error> {
+        let access = self.get_access(conn, user).await?;
+        let acting_user_id = match &access {
+            TicketFlowAccess::Denied | TicketFlowAccess::Conflicted => return Ok(access),
+            TicketFlowAccess::OnBehalfOf(user_id) => user_id.clone(),
+            TicketFlowAccess::Direct | TicketFlowAccess::Open => user.id.clone(),
+        };
+
+        let schema_flow = TicketSchemaFlow::find(conn, self.ticket_schema_flow_id).await?;
+        if acting_user_id == user.id {
+            let target = Target::find(conn, schema_flow.operator_id).await?;
+            if !Target::is_user_in_targets(conn, user, &vec![target]).await? {
+                return Ok(TicketFlowAccess::Denied);
+            }
+        }
+
+        let reviews: i64 = TicketSchemaReview::belonging_to(&schema_flow)
+            .count()
+            .get_result(conn)
+            .await?;
+        if reviews > 0
+            && TicketConflict::is_conflicted(conn, self.ticket_id, &acting_user_id).await?
+        {
+            return Ok(TicketFlowAccess::Conflicted);
+        }
+        Ok(access)
+    }
+
+    pub async fn is_assignee(
+        &self,
+        conn: &mut crate::DbConn,
+        user: &User,
+    ) -> Result<Option<bool>, diesel::result::Error> {
+        let assignees = TicketFlowAssignee::get_flow_assignees(conn, self).await?;
+        if assignees.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            assignees.iter().any(|assignee| assignee.id == user.id),
+        ))
+    }
+
+    pub async fn assign(
+        &mut self,
+        conn: &mut crate::DbConn,
+    ) -> Result<Vec<User>, diesel::result::Error> {
+        let schema_flow = TicketSchemaFlow::find(conn, self.ticket_schema_flow_id).await?;
+        let assignment = match &schema_flow.assignment {
+            Some(assignment) => assignment,
+            None => return Ok(vec![]),
+        };
+
+        let ticket = Ticket::find(conn, self.ticket_id).await?;
+        let candidates = schema_flow
+            .get_probably_assign_users(conn, Some(&ticket))
+            .await?;
+        let assigned_count =
+            TicketFlowAssignee::count_schema_flow_assignments(conn, &schema_flow).await?;
+        let loads = TicketFlowAssignee::get_schema_flow_loads(conn, &schema_flow).await?;
+        let users = assignment.pick(candidates, assigned_count, &loads);
+
+        let _ = TicketFlowAssignee::delete_flow_assignees(conn, self).await?;
+        let _ = TicketFlowAssignee::create(conn, self, &users).await?;
+
+        self.user_id = match users.as_slice() {
+            [user] => Some(user.id.clone()),
+            _ => None,
+        };
+        self.claimed = false;
+        let _ = self.save(conn).await?;
+
+        Ok(users)
+    }
+
+    pub async fn lock(
+        &self,
+        conn: &mut crate::DbConn,
+    ) -> Result<TicketFlow, diesel::result::Error> {
+        ticket_flows::table
+            .find(self.id)
+            .for_update()
+            .select(TicketFlow::as_select())
+            .first(conn)
+            .await
+    }
+
+    pub async fn bump_version(
+        &mut self,
+        conn: &mut crate::DbConn,
+    ) -> Result<usize, diesel::result::Error> {
+        let result = diesel::update(ticket_flows::table.find(self.id))
+            .set(ticket_flows::version.eq(ticket_flows::version + 1))
+            .execute(conn)
+            .await?;
+        self.version += 1;
+        Ok(result)
+    }
+
+    pub async fn get_overdue_flows(
+        conn: &mut crate::DbConn,
+    ) -> Result<Vec<TicketFlow>, diesel::result::Error> {
+        ticket_flows::table
+            .filter(ticket_flows::finished.eq(false))
+            .filter(ticket_flows::due_at.lt(chrono::Utc::now().naive_utc()))
+            .filter(ticket_flows::escalated_at.is_null())
+            .select(TicketFlow::as_select())
+            .load(conn)
+            .await
+    }
+
+    pub fn reset_due(&mut self) {
+        self.due_at = None;
+        self.escalated_at = None;
+    }
+
+    pub async fn set_escalated(
+        &mut self,
+        conn: &mut crate::DbConn,
+    ) -> Result<usize, diesel::result::Error> {
+        let escalated_at = chrono::Utc::now().naive_utc();
+        let result = diesel::update(ticket_flows::table.find(self.id))
+            .set(ticket_flows::escalated_at.eq(escalated_at))
+            .execute(conn)
+            .await?;
+        self.escalated_at = Some(escalated_at);
+        Ok(result)
+    }
+
     pub async fn save(&self, conn: &mut crate::DbConn) -> Result<usize, diesel::result::Error> {
         match diesel::replace_into(ticket_flows::table)
             .values(self)
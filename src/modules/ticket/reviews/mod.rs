@@ -1 +1,16 @@
+use self::models::TicketReview;
+use self::votes::TicketReviewTally;
+
 pub mod models;
+pub mod rubrics;
+pub mod rules;
+pub mod votes;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketFlowReview {
+    #[serde(flatten)]
+    pub review: TicketReview,
+    pub votes: Vec<TicketReview>,
+    pub tally: Option<TicketReviewTally>,
+    pub score: Option<f64>,
+}
@@ -1,11 +1,16 @@
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
+use rocket::serde::json::serde_json;
 use rocket_db_pools::diesel::prelude::RunQueryDsl;
 
 use crate::modules::ticket::models::{TicketFlow, TicketSchemaFlow};
-use crate::schema::{ticket_reviews, ticket_schema_reviews};
+use crate::schema::{ticket_flows, ticket_reviews, ticket_schema_reviews};
 use crate::utils::serde::unix_time;
 
+use super::rubrics::ReviewRubric;
+use super::rules::ReviewRules;
+use super::votes::ReviewVoteDefine;
+
 #[derive(
     Queryable,
     Identifiable,
@@ -29,6 +34,11 @@ pub struct TicketSchemaReview {
     pub created_at: NaiveDateTime,
     #[serde(with = "unix_time")]
     pub updated_at: NaiveDateTime,
+    pub vote: Option<ReviewVoteDefine>,
+    pub rubric: Option<ReviewRubric>,
+    #[serde(default)]
+    pub anonymous: bool,
+    pub rules: Option<ReviewRules>,
 }
 
 impl TicketSchemaReview {
@@ -36,11 +46,19 @@ impl TicketSchemaReview {
         conn: &mut crate::DbConn,
         schema_flow: &TicketSchemaFlow,
         restarted: bool,
+        vote: Option<ReviewVoteDefine>,
+        rubric: Option<ReviewRubric>,
+        anonymous: bool,
+        rules: Option<ReviewRules>,
     ) -> Result<TicketSchemaReview, diesel::result::Error> {
         diesel::insert_into(ticket_schema_reviews::table)
             .values((
                 ticket_schema_reviews::ticket_schema_flow_id.eq(schema_flow.id),
                 ticket_schema_reviews::restarted.eq(restarted),
+                ticket_schema_reviews::vote.eq(vote),
+                ticket_schema_reviews::rubric.eq(rubric),
+                ticket_schema_reviews::anonymous.eq(anonymous),
+                ticket_schema_reviews::rules.eq(rules),
             ))
             .execute(conn)
             .await?;
@@ -55,6 +73,18 @@ impl TicketSchemaReview {
             .await
     }
 
+    pub async fn get_ticket_reviews(
+        &self,
+        conn: &mut crate::DbConn,
+    ) -> Result<Vec<(i32, TicketReview)>, diesel::result::Error> {
+        ticket_reviews::table
+            .inner_join(ticket_flows::table)
+            .filter(ticket_reviews::ticket_schema_review_id.eq(self.id))
+            .select((ticket_flows::ticket_id, TicketReview::as_select()))
+            .load(conn)
+            .await
+    }
+
     pub async fn save(&self, conn: &mut crate::DbConn) -> Result<usize, diesel::result::Error> {
         match diesel::replace_into(ticket_schema_reviews::table)
             .values(self)
@@ -88,6 +118,7 @@ impl TicketSchemaReview {
     Deserialize,
     AsChangeset,
     Insertable,
+    Clone,
 )]
 #[diesel(belongs_to(TicketFlow))]
 #[diesel(belongs_to(TicketSchemaReview))]
@@ -103,6 +134,8 @@ pub struct TicketReview {
     pub created_at: NaiveDateTime,
     #[serde(with = "unix_time")]
     pub updated_at: NaiveDateTime,
+    pub user_id: Option<String>,
+    pub scores: Option<serde_json::Value>,
 }
 
 impl TicketReview {
@@ -110,19 +143,32 @@ impl TicketReview {
         conn: &mut crate::DbConn,
         ticket_flow: &TicketFlow,
         ticket_schema_review: &TicketSchemaReview,
+        user_id: Option<String>,
         approved: bool,
         comment: Option<String>,
+        scores: Option<serde_json::Value>,
     ) -> Result<TicketReview, diesel::result::Error> {
-        let ticket_review: Result<TicketReview, _> = ticket_reviews::table
+        let _ = ticket_flow.lock(conn).await?;
+        let mut query = ticket_reviews::table
             .filter(ticket_reviews::ticket_flow_id.eq(ticket_flow.id))
             .filter(ticket_reviews::ticket_schema_review_id.eq(ticket_schema_review.id))
-            .first(conn)
-            .await;
+            .into_boxed();
+
+        if ticket_schema_review.vote.is_some() {
+            query = match &user_id {
+                Some(user_id) => query.filter(ticket_reviews::user_id.eq(user_id)),
+                None => query.filter(ticket_reviews::user_id.is_null()),
+            };
+        }
+
+        let ticket_review: Result<TicketReview, _> = query.first(conn).await;
 
         match ticket_review {
             Ok(mut review) => {
                 review.approved = approved;
                 review.comment = comment;
+                review.user_id = user_id;
+                review.scores = scores;
                 review.save(conn).await?;
                 Ok(review)
             }
@@ -133,6 +179,8 @@ impl TicketReview {
                         ticket_reviews::ticket_schema_review_id.eq(ticket_schema_review.id),
                         ticket_reviews::approved.eq(approved),
                         ticket_reviews::comment.eq(comment),
+                        ticket_reviews::user_id.eq(user_id),
+                        ticket_reviews::scores.eq(scores),
                     ))
                     .execute(conn)
                     .await?;
@@ -150,6 +198,28 @@ impl TicketReview {
         }
     }
 
+    pub async fn get_flow_reviews(
+        conn: &mut crate::DbConn,
+        ticket_flow: &TicketFlow,
+    ) -> Result<Vec<TicketReview>, diesel::result::Error> {
+        TicketReview::belonging_to(ticket_flow)
+            .order(ticket_reviews::updated_at.asc())
+            .select(TicketReview::as_select())
+            .load(conn)
+            .await
+    }
+
+    pub async fn delete_flow_reviews(
+        conn: &mut crate::DbConn,
+        ticket_flow: &TicketFlow,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(
+            ticket_reviews::table.filter(ticket_reviews::ticket_flow_id.eq(ticket_flow.id)),
+        )
+        .execute(conn)
+        .await
+    }
+
     pub async fn save(&self, conn: &mut crate::DbConn) -> Result<usize, diesel::result::Error> {
         match diesel::replace_into(ticket_reviews::table)
             .values(self)
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use diesel::{
+    deserialize::FromSql,
+    mysql::{Mysql, MysqlValue},
+    serialize::ToSql,
+    sql_types,
+};
+use rocket::serde::json::serde_json;
+use serde_json::Value;
+
+use crate::utils::i18n::I18n;
+
+use super::models::TicketReview;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ReviewRubricCriterion {
+    pub key: String,
+    pub name_zh: String,
+    pub name_en: String,
+    pub min: f64,
+    pub max: f64,
+    pub weight: f64,
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::Json)]
+pub struct ReviewRubric {
+    pub criteria: Vec<ReviewRubricCriterion>,
+}
+
+impl FromSql<sql_types::Json, Mysql> for ReviewRubric {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<ReviewRubric>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for ReviewRubric {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct TicketReviewScore {
+    pub ticket_id: i32,
+    pub rank: usize,
+    pub score: f64,
+    pub criteria: HashMap<String, f64>,
+    pub reviews: usize,
+}
+
+impl ReviewRubric {
+    pub fn is_valid(&self) -> bool {
+        !self.criteria.is_empty()
+            && self.criteria.iter().enumerate().all(|(index, criterion)| {
+                criterion.min <= criterion.max
+                    && criterion.weight >= 0.0
+                    && self.criteria[..index]
+                        .iter()
+                        .all(|other| other.key != criterion.key)
+            })
+            && self.criteria.iter().any(|criterion| criterion.weight > 0.0)
+    }
+
+    pub fn validate_and_normalize<'a>(
+        &self,
+        i18n: &I18n<'a>,
+        data: &serde_json::Map<String, Value>,
+    ) -> Result<serde_json::Map<String, Value>, serde_json::Map<String, Value>> {
+        let mut result = serde_json::Map::new();
+        let mut errors = serde_json::Map::new();
+
+        for criterion in self.criteria.iter() {
+            let score = match data.get(&criterion.key) {
+                Some(Value::Number(score)) => score.as_f64(),
+                Some(_) => {
+                    errors.insert(
+                        criterion.key.clone(),
+                        Value::String(
+                            i18n.tf("ticket.rules.unknown", &[("field", criterion.key.clone())]),
+                        ),
+                    );
+                    continue;
+                }
+                None => {
+                    errors.insert(
+                        criterion.key.clone(),
+                        Value::String(
+                            i18n.tf("ticket.rules.required", &[("field", criterion.key.clone())]),
+                        ),
+                    );
+                    continue;
+                }
+            };
+            match score {
+                Some(score) if score >= criterion.min && score <= criterion.max => {
+                    result.insert(criterion.key.clone(), Value::from(score));
+                }
+                _ => {
+                    errors.insert(
+                        criterion.key.clone(),
+                        Value::String(i18n.tf(
+                            "ticket.rules.score_out_of_range",
+                            &[("field", criterion.key.clone())],
+                        )),
+                    );
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(result)
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn weighted_score(&self, scores: &Value) -> Option<f64> {
+        let mut total = 0.0;
+        let mut total_weight = 0.0;
+        for criterion in self.criteria.iter() {
+            let score = scores
+                .get(&criterion.key)
+                .and_then(|score| score.as_f64())?;
+            total += score * criterion.weight;
+            total_weight += criterion.weight;
+        }
+        if total_weight > 0.0 {
+            Some(total / total_weight)
+        } else {
+            None
+        }
+    }
+
+    pub fn average_score(&self, reviews: &[TicketReview]) -> Option<f64> {
+        let scores = reviews
+            .iter()
+            .filter_map(|review| review.scores.as_ref())
+            .filter_map(|scores| self.weighted_score(scores))
+            .collect::<Vec<_>>();
+        if scores.is_empty() {
+            None
+        } else {
+            Some(scores.iter().sum::<f64>() / scores.len() as f64)
+        }
+    }
+
+    pub fn average_criteria(&self, reviews: &[TicketReview]) -> HashMap<String, f64> {
+        let mut result = HashMap::new();
+        for criterion in self.criteria.iter() {
+            let scores = reviews
+                .iter()
+                .filter_map(|review| review.scores.as_ref())
+                .filter_map(|scores| scores.get(&criterion.key).and_then(|score| score.as_f64()))
+                .collect::<Vec<_>>();
+            if !scores.is_empty() {
+                result.insert(
+                    criterion.key.clone(),
+                    scores.iter().sum::<f64>() / scores.len() as f64,
+                );
+            }
+        }
+        result
+    }
+
+    pub fn rank(&self, reviews: Vec<(i32, TicketReview)>) -> Vec<TicketReviewScore> {
+        let mut grouped: HashMap<i32, Vec<TicketReview>> = HashMap::new();
+        for (ticket_id, review) in reviews {
+            grouped.entry(ticket_id).or_default().push(review);
+        }
+
+        let mut result = grouped
+            .into_iter()
+            .filter_map(|(ticket_id, reviews)| {
+                let score = self.average_score(&reviews)?;
+                Some(TicketReviewScore {
+                    ticket_id,
+                    rank: 0,
+                    score,
+                    criteria: self.average_criteria(&reviews),
+                    reviews: reviews
+                        .iter()
+                        .filter(|review| review.scores.is_some())
+                        .count(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        result.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.ticket_id.cmp(&b.ticket_id))
+        });
+
+        let mut previous: Option<f64> = None;
+        for index in 0..result.len() {
+            result[index].rank = match previous {
+                Some(score) if score == result[index].score => result[index - 1].rank,
+                _ => index + 1,
+            };
+            previous = Some(result[index].score);
+        }
+
+        result
+    }
+}
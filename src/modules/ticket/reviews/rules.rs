@@ -0,0 +1,165 @@
+use diesel::{
+    deserialize::FromSql,
+    mysql::{Mysql, MysqlValue},
+    serialize::ToSql,
+    sql_types,
+};
+use rocket::serde::json::serde_json;
+
+use crate::modules::ticket::forms::conditions::FormFieldCondition;
+use crate::modules::ticket::forms::fields::FormFieldDefault;
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum LegacyReviewRuleCondition {
+    Missing { from: FormFieldDefault },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ReviewRuleConditionValue {
+    Condition(FormFieldCondition),
+    Legacy(LegacyReviewRuleCondition),
+}
+
+impl From<ReviewRuleConditionValue> for ReviewRuleCondition {
+    fn from(value: ReviewRuleConditionValue) -> Self {
+        match value {
+            ReviewRuleConditionValue::Condition(condition) => ReviewRuleCondition(condition),
+            ReviewRuleConditionValue::Legacy(LegacyReviewRuleCondition::Missing { from }) => {
+                ReviewRuleCondition(FormFieldCondition::Empty { from })
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(from = "ReviewRuleConditionValue")]
+pub struct ReviewRuleCondition(pub FormFieldCondition);
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ReviewRule {
+    pub condition: ReviewRuleCondition,
+    pub approved: bool,
+    pub comment: Option<String>,
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::Json)]
+pub struct ReviewRules(pub Vec<ReviewRule>);
+
+impl FromSql<sql_types::Json, Mysql> for ReviewRules {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<ReviewRules>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for ReviewRules {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+impl ReviewRules {
+    pub async fn resolve(&self, conn: &mut crate::DbConn, ticket_id: &i32) -> ReviewRules {
+        let mut rules = self.clone();
+        for rule in rules.0.iter_mut() {
+            rule.condition.0 = rule.condition.0.resolve(conn, ticket_id).await;
+        }
+        rules
+    }
+
+    pub fn decide(&self) -> Option<(usize, &ReviewRule)> {
+        self.0
+            .iter()
+            .enumerate()
+            .find(|(_, rule)| rule.condition.0.is_met())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::serde::json::serde_json::{self, json};
+
+    use super::{ReviewRuleCondition, ReviewRules};
+    use crate::modules::ticket::forms::conditions::FormFieldCondition;
+    use crate::modules::ticket::forms::fields::FormFieldDefault;
+
+    #[test]
+    fn deserialize_legacy_missing() {
+        let from = json!({
+            "type": "Dynamic",
+            "content": {
+                "schema_form_id": 1,
+                "flow_id": null,
+                "field_key": "reason",
+                "value": null
+            }
+        });
+        let condition: ReviewRuleCondition =
+            serde_json::from_value(json!({ "type": "Missing", "from": from })).unwrap();
+        assert_eq!(
+            condition,
+            ReviewRuleCondition(FormFieldCondition::Empty {
+                from: serde_json::from_value::<FormFieldDefault>(from).unwrap(),
+            })
+        );
+        assert!(condition.0.is_met());
+    }
+
+    #[test]
+    fn decide_first_matching_rule() {
+        let rules: ReviewRules = serde_json::from_value(json!([
+            {
+                "condition": {
+                    "type": "Equal",
+                    "from": { "type": "Static", "content": "a" },
+                    "value": ["b"]
+                },
+                "approved": true,
+                "comment": null
+            },
+            {
+                "condition": {
+                    "type": "NotEmpty",
+                    "from": { "type": "Static", "content": "a" }
+                },
+                "approved": false,
+                "comment": "rejected"
+            },
+            {
+                "condition": {
+                    "type": "Equal",
+                    "from": { "type": "Static", "content": "a" },
+                    "value": ["a"]
+                },
+                "approved": true,
+                "comment": null
+            }
+        ]))
+        .unwrap();
+        let (index, rule) = rules.decide().unwrap();
+        assert_eq!(index, 1);
+        assert!(!rule.approved);
+        assert_eq!(rule.comment.as_deref(), Some("rejected"));
+    }
+
+    #[test]
+    fn decide_without_match() {
+        let rules: ReviewRules = serde_json::from_value(json!([
+            {
+                "condition": {
+                    "type": "Empty",
+                    "from": { "type": "Static", "content": "a" }
+                },
+                "approved": true,
+                "comment": null
+            }
+        ]))
+        .unwrap();
+        assert!(rules.decide().is_none());
+        assert!(ReviewRules(vec![]).decide().is_none());
+    }
+}
@@ -0,0 +1,88 @@
+use diesel::{
+    deserialize::FromSql,
+    mysql::{Mysql, MysqlValue},
+    serialize::ToSql,
+    sql_types,
+};
+use rocket::serde::json::serde_json;
+
+use super::models::TicketReview;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(tag = "type", content = "content")]
+pub enum ReviewQuorum {
+    Count(u32),
+    All,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum ReviewRejectPolicy {
+    AnyReject,
+    Unreachable,
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::Json)]
+pub struct ReviewVoteDefine {
+    pub quorum: ReviewQuorum,
+    pub reject_policy: ReviewRejectPolicy,
+}
+
+impl FromSql<sql_types::Json, Mysql> for ReviewVoteDefine {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<ReviewVoteDefine>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for ReviewVoteDefine {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct TicketReviewTally {
+    pub approved: usize,
+    pub rejected: usize,
+    pub eligible: usize,
+    pub required: usize,
+    pub decision: Option<bool>,
+}
+
+impl ReviewVoteDefine {
+    pub fn required(&self, eligible: usize) -> usize {
+        let required = match self.quorum {
+            ReviewQuorum::Count(count) => (count as usize).min(eligible),
+            ReviewQuorum::All => eligible,
+        };
+        required.max(1)
+    }
+
+    pub fn tally(&self, votes: &[TicketReview], eligible: usize) -> TicketReviewTally {
+        let approved = votes.iter().filter(|v| v.approved).count();
+        let rejected = votes.len() - approved;
+        let required = self.required(eligible);
+
+        let decision = if approved >= required {
+            Some(true)
+        } else {
+            match self.reject_policy {
+                ReviewRejectPolicy::AnyReject if rejected > 0 => Some(false),
+                ReviewRejectPolicy::Unreachable if eligible.saturating_sub(rejected) < required => {
+                    Some(false)
+                }
+                _ => None,
+            }
+        };
+
+        TicketReviewTally {
+            approved,
+            rejected,
+            eligible,
+            required,
+            decision,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use diesel::{
+    deserialize::FromSql,
+    mysql::{Mysql, MysqlValue},
+    serialize::ToSql,
+    sql_types,
+};
+use rocket::serde::json::serde_json;
+
+use super::forms::fields::{FormFieldDefault, FormFieldValue};
+
+pub mod models;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct TicketSpawnMapping {
+    pub from: FormFieldDefault,
+    pub schema_form_id: i32,
+    pub field_key: String,
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::Json)]
+pub struct TicketSpawnMappings(pub Vec<TicketSpawnMapping>);
+
+impl FromSql<sql_types::Json, Mysql> for TicketSpawnMappings {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<TicketSpawnMappings>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for TicketSpawnMappings {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(tag = "type")]
+pub enum TicketSpawnOperatorFrom {
+    User { user_id: String },
+    Flow { schema_flow_id: i32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct TicketSpawnOperator {
+    pub schema_flow_id: i32,
+    pub from: TicketSpawnOperatorFrom,
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::Json)]
+pub struct TicketSpawnOperators(pub Vec<TicketSpawnOperator>);
+
+impl FromSql<sql_types::Json, Mysql> for TicketSpawnOperators {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<TicketSpawnOperators>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for TicketSpawnOperators {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct TicketPrefillValue {
+    pub schema_form_id: i32,
+    pub field_key: String,
+    pub value: FormFieldValue,
+}
+
+#[derive(FromSqlRow, AsExpression, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[diesel(sql_type = sql_types::Json)]
+pub struct TicketPrefill(pub Vec<TicketPrefillValue>);
+
+impl FromSql<sql_types::Json, Mysql> for TicketPrefill {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<sql_types::Json, Mysql>>::from_sql(bytes)?;
+        Ok(serde_json::from_value::<TicketPrefill>(value)?)
+    }
+}
+
+impl ToSql<sql_types::Json, Mysql> for TicketPrefill {
+    fn to_sql(&self, out: &mut diesel::serialize::Output<Mysql>) -> diesel::serialize::Result {
+        let value = serde_json::to_value(self)?;
+        <serde_json::Value as ToSql<sql_types::Json, Mysql>>::to_sql(&value, &mut out.reborrow())
+    }
+}
+
+impl TicketPrefill {
+    pub fn get(&self, schema_form_id: i32, field_key: &String) -> Option<&FormFieldValue> {
+        self.0
+            .iter()
+            .find(|prefill| {
+                prefill.schema_form_id == schema_form_id && prefill.field_key == *field_key
+            })
+            .map(|prefill| &prefill.value)
+    }
+}
+
+impl TicketSpawnMappings {
+    pub async fn resolve(&self, conn: &mut crate::DbConn, ticket_id: &i32) -> TicketPrefill {
+        let mut result = vec![];
+        for mapping in self.0.iter() {
+            let value = match mapping.from.resolve(conn, ticket_id).await {
+                FormFieldDefault::Static(value) => Some(value),
+                FormFieldDefault::Dynamic { value, .. } => value,
+            };
+            if let Some(value) = value {
+                result.push(TicketPrefillValue {
+                    schema_form_id: mapping.schema_form_id,
+                    field_key: mapping.field_key.clone(),
+                    value,
+                });
+            }
+        }
+        TicketPrefill(result)
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rocket::serde::json::serde_json;
+use rocket_db_pools::diesel::prelude::RunQueryDsl;
+
+use crate::modules::ticket::histories::models::TicketHistory;
+use crate::modules::ticket::histories::TicketHistoryAction;
+use crate::modules::ticket::models::{Ticket, TicketSchema};
+use crate::schema::{ticket_flows, ticket_schema_spawns, tickets};
+use crate::utils::serde::unix_time;
+
+use super::{TicketSpawnMappings, TicketSpawnOperatorFrom, TicketSpawnOperators};
+
+#[derive(
+    Queryable,
+    Identifiable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Insertable,
+    AsChangeset,
+)]
+#[diesel(belongs_to(TicketSchema))]
+#[diesel(table_name = ticket_schema_spawns)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct TicketSchemaSpawn {
+    pub id: i32,
+    pub ticket_schema_id: i32,
+    pub target_schema_id: i32,
+    pub mappings: TicketSpawnMappings,
+    pub operators: TicketSpawnOperators,
+    #[serde(with = "unix_time")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "unix_time")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl TicketSchemaSpawn {
+    pub async fn create(
+        conn: &mut crate::DbConn,
+        schema: &TicketSchema,
+        target_schema: &TicketSchema,
+        mappings: TicketSpawnMappings,
+        operators: TicketSpawnOperators,
+    ) -> Result<TicketSchemaSpawn, diesel::result::Error> {
+        diesel::insert_into(ticket_schema_spawns::table)
+            .values((
+                ticket_schema_spawns::ticket_schema_id.eq(schema.id),
+                ticket_schema_spawns::target_schema_id.eq(target_schema.id),
+                ticket_schema_spawns::mappings.eq(mappings),
+                ticket_schema_spawns::operators.eq(operators),
+            ))
+            .execute(conn)
+            .await?;
+
+        sql_function! {
+            fn last_insert_id() -> Integer;
+        }
+
+        ticket_schema_spawns::table
+            .find(last_insert_id())
+            .first(conn)
+            .await
+    }
+
+    pub async fn find(
+        conn: &mut crate::DbConn,
+        id: i32,
+    ) -> Result<TicketSchemaSpawn, diesel::result::Error> {
+        ticket_schema_spawns::table.find(id).first(conn).await
+    }
+
+    pub async fn delete(&self, conn: &mut crate::DbConn) -> Result<usize, diesel::result::Error> {
+        diesel::delete(ticket_schema_spawns::table.find(self.id))
+            .execute(conn)
+            .await
+    }
+
+    pub async fn get_schema_spawns(
+        conn: &mut crate::DbConn,
+        schema_id: i32,
+    ) -> Result<Vec<TicketSchemaSpawn>, diesel::result::Error> {
+        ticket_schema_spawns::table
+            .filter(ticket_schema_spawns::ticket_schema_id.eq(schema_id))
+            .order(ticket_schema_spawns::id.asc())
+            .select(TicketSchemaSpawn::as_select())
+            .load(conn)
+            .await
+    }
+
+    pub async fn is_reachable(
+        conn: &mut crate::DbConn,
+        from_schema_id: i32,
+        to_schema_id: i32,
+    ) -> Result<bool, diesel::result::Error> {
+        let edges: Vec<(i32, i32)> = ticket_schema_spawns::table
+            .select((
+                ticket_schema_spawns::ticket_schema_id,
+                ticket_schema_spawns::target_schema_id,
+            ))
+            .load(conn)
+            .await?;
+
+        Ok(has_path(&edges, from_schema_id, to_schema_id))
+    }
+
+    pub async fn spawn(
+        &self,
+        conn: &mut crate::DbConn,
+        ticket: &Ticket,
+    ) -> Result<Ticket, diesel::result::Error> {
+        let target_schema = TicketSchema::find(conn, self.target_schema_id).await?;
+        let flows = target_schema.get_flows(conn).await?;
+        let prefill = self.mappings.resolve(conn, &ticket.id).await;
+
+        let mut assign_flow_users = HashMap::new();
+        for operator in self.operators.0.iter() {
+            let user_id = match &operator.from {
+                TicketSpawnOperatorFrom::User { user_id } => Some(user_id.clone()),
+                TicketSpawnOperatorFrom::Flow { schema_flow_id } => ticket_flows::table
+                    .filter(ticket_flows::ticket_id.eq(ticket.id))
+                    .filter(ticket_flows::ticket_schema_flow_id.eq(schema_flow_id))
+                    .select(ticket_flows::user_id)
+                    .first::<Option<String>>(conn)
+                    .await
+                    .optional()?
+                    .flatten(),
+            };
+            if let Some(user_id) = user_id {
+                assign_flow_users.insert(operator.schema_flow_id, user_id);
+            }
+        }
+
+        let mut child = Ticket::create(conn, &target_schema, &ticket.title).await?;
+        let _ = child.set_parent(conn, ticket, prefill).await?;
+        child.fill_flows(conn, &flows, assign_flow_users).await?;
+        Box::pin(child.activate_process_flow(conn)).await?;
+
+        let _ = TicketHistory::create(
+            conn,
+            ticket.id,
+            None,
+            None,
+            TicketHistoryAction::Spawn,
+            Some(serde_json::json!({ "ticket_id": child.id })),
+        )
+        .await?;
+
+        Ok(child)
+    }
+
+    pub async fn spawn_tickets(
+        conn: &mut crate::DbConn,
+        ticket: &Ticket,
+    ) -> Result<Vec<Ticket>, diesel::result::Error> {
+        let spawns = TicketSchemaSpawn::get_schema_spawns(conn, ticket.ticket_schema_id).await?;
+        if spawns.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let spawned_schema_ids: Vec<i32> = tickets::table
+            .filter(tickets::parent_id.eq(ticket.id))
+            .select(tickets::ticket_schema_id)
+            .load(conn)
+            .await?;
+
+        let mut result = vec![];
+        for spawn in spawns.iter() {
+            if spawned_schema_ids.contains(&spawn.target_schema_id) {
+                continue;
+            }
+            result.push(spawn.spawn(conn, ticket).await?);
+        }
+
+        Ok(result)
+    }
+}
+
+fn has_path(edges: &[(i32, i32)], from_schema_id: i32, to_schema_id: i32) -> bool {
+    let mut visited = vec![from_schema_id];
+    let mut pending = vec![from_schema_id];
+    while let Some(schema_id) = pending.pop() {
+        if schema_id == to_schema_id {
+            return true;
+        }
+        for (_, target_schema_id) in edges.iter().filter(|(id, _)| *id == schema_id) {
+            if !visited.contains(target_schema_id) {
+                visited.push(*target_schema_id);
+                pending.push(*target_schema_id);
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::has_path;
+
+    #[test]
+    fn has_path_follows_edges() {
+        let edges = [(1, 2), (2, 3), (4, 1)];
+        assert!(has_path(&edges, 1, 3));
+        assert!(has_path(&edges, 4, 3));
+        assert!(!has_path(&edges, 3, 1));
+        assert!(!has_path(&edges, 1, 4));
+        assert!(has_path(&edges, 5, 5));
+    }
+
+    #[test]
+    fn has_path_detects_cycles() {
+        let edges = [(1, 2), (2, 3)];
+        // Adding 3 -> 1 closes a cycle because 1 already reaches 3.
+        assert!(has_path(&edges, 1, 3));
+        // Adding 1 -> 4 does not, because 4 cannot reach 1.
+        assert!(!has_path(&edges, 4, 1));
+    }
+
+    #[test]
+    fn has_path_terminates_on_existing_cycles() {
+        let edges = [(1, 2), (2, 1), (2, 3)];
+        assert!(has_path(&edges, 1, 3));
+        assert!(!has_path(&edges, 1, 4));
+    }
+}
@@ -70,6 +70,34 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    ticket_conflicts (id) {
+        id -> Integer,
+        ticket_schema_id -> Integer,
+        ticket_id -> Nullable<Integer>,
+        #[max_length = 36]
+        user_id -> Char,
+        #[max_length = 100]
+        field_key -> Nullable<Varchar>,
+        #[max_length = 255]
+        field_value -> Nullable<Varchar>,
+        reason -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    ticket_flow_assignees (id) {
+        id -> Integer,
+        ticket_flow_id -> Integer,
+        #[max_length = 36]
+        user_id -> Char,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     ticket_flows (id) {
         id -> Integer,
@@ -80,6 +108,11 @@ diesel::table! {
         finished -> Bool,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        due_at -> Nullable<Timestamp>,
+        escalated_at -> Nullable<Timestamp>,
+        version -> Integer,
+        skipped -> Bool,
+        claimed -> Bool,
     }
 }
 
@@ -94,6 +127,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    ticket_form_drafts (id) {
+        id -> Integer,
+        ticket_flow_id -> Integer,
+        ticket_schema_form_id -> Integer,
+        #[max_length = 36]
+        user_id -> Char,
+        value -> Json,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     ticket_form_files (id) {
         #[max_length = 64]
@@ -126,6 +172,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    ticket_histories (id) {
+        id -> Integer,
+        ticket_id -> Integer,
+        ticket_flow_id -> Nullable<Integer>,
+        #[max_length = 36]
+        user_id -> Nullable<Char>,
+        #[max_length = 50]
+        action -> Varchar,
+        detail -> Nullable<Json>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     ticket_reviews (id) {
         id -> Integer,
@@ -135,6 +196,9 @@ diesel::table! {
         comment -> Nullable<Text>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        #[max_length = 36]
+        user_id -> Nullable<Char>,
+        scores -> Nullable<Json>,
     }
 }
 
@@ -150,6 +214,9 @@ diesel::table! {
         updated_at -> Timestamp,
         #[max_length = 100]
         name_en -> Varchar,
+        assignment -> Nullable<Json>,
+        due_hours -> Nullable<Integer>,
+        condition -> Nullable<Json>,
     }
 }
 
@@ -171,6 +238,7 @@ diesel::table! {
         #[max_length = 100]
         name_en -> Varchar,
         description_en -> Text,
+        identifying -> Bool,
     }
 }
 
@@ -181,6 +249,7 @@ diesel::table! {
         expired_at -> Nullable<Timestamp>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        rules -> Nullable<Json>,
     }
 }
 
@@ -201,6 +270,22 @@ diesel::table! {
         restarted -> Bool,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        vote -> Nullable<Json>,
+        rubric -> Nullable<Json>,
+        anonymous -> Bool,
+        rules -> Nullable<Json>,
+    }
+}
+
+diesel::table! {
+    ticket_schema_spawns (id) {
+        id -> Integer,
+        ticket_schema_id -> Integer,
+        target_schema_id -> Integer,
+        mappings -> Json,
+        operators -> Json,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -217,6 +302,7 @@ diesel::table! {
         #[max_length = 100]
         title_en -> Varchar,
         description_en -> Text,
+        dates -> Nullable<Json>,
     }
 }
 
@@ -229,6 +315,22 @@ diesel::table! {
         finished -> Bool,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        parent_id -> Nullable<Integer>,
+        prefill -> Nullable<Json>,
+    }
+}
+
+diesel::table! {
+    user_delegations (id) {
+        id -> Integer,
+        #[max_length = 36]
+        user_id -> Char,
+        #[max_length = 36]
+        delegate_id -> Char,
+        started_at -> Timestamp,
+        ended_at -> Timestamp,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -291,15 +393,27 @@ diesel::joinable!(role_managers -> targets (target_id));
 diesel::joinable!(roles -> projects (project_id));
 diesel::joinable!(targets -> labels (label_id));
 diesel::joinable!(targets -> users (user_id));
+diesel::joinable!(ticket_conflicts -> ticket_schemas (ticket_schema_id));
+diesel::joinable!(ticket_conflicts -> tickets (ticket_id));
+diesel::joinable!(ticket_conflicts -> users (user_id));
+diesel::joinable!(ticket_flow_assignees -> ticket_flows (ticket_flow_id));
+diesel::joinable!(ticket_flow_assignees -> users (user_id));
 diesel::joinable!(ticket_flows -> ticket_schema_flows (ticket_schema_flow_id));
 diesel::joinable!(ticket_flows -> tickets (ticket_id));
 diesel::joinable!(ticket_flows -> users (user_id));
 diesel::joinable!(ticket_form_answers -> ticket_flows (ticket_flow_id));
 diesel::joinable!(ticket_form_answers -> ticket_schema_forms (ticket_schema_form_id));
+diesel::joinable!(ticket_form_drafts -> ticket_flows (ticket_flow_id));
+diesel::joinable!(ticket_form_drafts -> ticket_schema_forms (ticket_schema_form_id));
+diesel::joinable!(ticket_form_drafts -> users (user_id));
 diesel::joinable!(ticket_form_files -> ticket_schema_form_fields (ticket_schema_form_field_id));
 diesel::joinable!(ticket_form_images -> ticket_schema_form_fields (ticket_schema_form_field_id));
+diesel::joinable!(ticket_histories -> ticket_flows (ticket_flow_id));
+diesel::joinable!(ticket_histories -> tickets (ticket_id));
+diesel::joinable!(ticket_histories -> users (user_id));
 diesel::joinable!(ticket_reviews -> ticket_flows (ticket_flow_id));
 diesel::joinable!(ticket_reviews -> ticket_schema_reviews (ticket_schema_review_id));
+diesel::joinable!(ticket_reviews -> users (user_id));
 diesel::joinable!(ticket_schema_flows -> targets (operator_id));
 diesel::joinable!(ticket_schema_flows -> ticket_schemas (ticket_schema_id));
 diesel::joinable!(ticket_schema_form_fields -> ticket_schema_forms (ticket_schema_form_id));
@@ -321,18 +435,24 @@ diesel::allow_tables_to_appear_in_same_query!(
     role_managers,
     roles,
     targets,
+    ticket_conflicts,
+    ticket_flow_assignees,
     ticket_flows,
     ticket_form_answers,
+    ticket_form_drafts,
     ticket_form_files,
     ticket_form_images,
+    ticket_histories,
     ticket_reviews,
     ticket_schema_flows,
     ticket_schema_form_fields,
     ticket_schema_forms,
     ticket_schema_managers,
     ticket_schema_reviews,
+    ticket_schema_spawns,
     ticket_schemas,
     tickets,
+    user_delegations,
     user_emails,
     user_sessions,
     users,
@@ -0,0 +1,86 @@
+pub fn parse(text: &str) -> Option<Vec<Vec<String>>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut cell = String::new();
+    let mut in_quotes = false;
+
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    cell.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => cell.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut cell)),
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => cell.push(c),
+        }
+    }
+
+    if in_quotes {
+        return None;
+    }
+
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+
+    rows.retain(|row| row.iter().any(|cell| !cell.trim().is_empty()));
+    Some(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parse_rows() {
+        assert_eq!(
+            parse("title,score\r\nfirst,1\nsecond,2\n"),
+            Some(vec![
+                vec!["title".to_owned(), "score".to_owned()],
+                vec!["first".to_owned(), "1".to_owned()],
+                vec!["second".to_owned(), "2".to_owned()],
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_quoted_cells() {
+        assert_eq!(
+            parse("\u{feff}\"a, b\",\"say \"\"hi\"\"\",\"line\nbreak\""),
+            Some(vec![vec![
+                "a, b".to_owned(),
+                "say \"hi\"".to_owned(),
+                "line\nbreak".to_owned(),
+            ]])
+        );
+    }
+
+    #[test]
+    fn parse_skips_blank_rows() {
+        assert_eq!(
+            parse("a\n\n ,\nb"),
+            Some(vec![vec!["a".to_owned()], vec!["b".to_owned()]])
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unterminated_quote() {
+        assert_eq!(parse("title\n\"unterminated"), None);
+    }
+}
@@ -1,10 +1,9 @@
 use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
-use rocket::State;
 
 use crate::AppConfig;
 
 pub async fn send_email(
-    config: &State<AppConfig>,
+    config: &AppConfig,
     message: Message,
 ) -> Result<(), lettre::transport::smtp::Error> {
     // Open a remote connection to gmail
@@ -14,8 +13,5 @@ pub async fn send_email(
             .build();
 
     // Send the email
-    match mailer.send(message).await {
-        Ok(_) => return Ok(()),
-        Err(e) => panic!("Could not send email: {e:?}"),
-    }
+    mailer.send(message).await.map(|_| ())
 }
@@ -1,3 +1,4 @@
+pub mod csv;
 pub mod file;
 pub mod i18n;
 pub mod image;
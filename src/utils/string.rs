@@ -1,9 +1,21 @@
+use unicode_segmentation::UnicodeSegmentation;
+
 pub trait StringExt {
     fn count_words(&self, word: &str) -> u32;
+    fn count_graphemes(&self) -> u32;
+    fn count_unicode_words(&self) -> u32;
 }
 
 impl StringExt for str {
     fn count_words(&self, word: &str) -> u32 {
         self.split(word).count() as u32
     }
+
+    fn count_graphemes(&self) -> u32 {
+        self.graphemes(true).count() as u32
+    }
+
+    fn count_unicode_words(&self) -> u32 {
+        self.unicode_words().count() as u32
+    }
 }